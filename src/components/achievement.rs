@@ -0,0 +1,301 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{ACHIEVEMENT_UNLOCKS_FILE, PelletType};
+
+/// 成就的解锁条件，在定义文件中以 ron 格式书写
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AchievementCondition {
+    /// 一颗能量豆内吃掉这么多只幽灵
+    GhostCombo(u32),
+    /// 一次都不死完成一关
+    ClearLevelWithoutDying,
+    /// 不吃能量豆完成一关
+    ClearLevelWithoutPowerPellet,
+    /// 一条命在追捕模式下存活这么多秒
+    SurviveChase(f32),
+    /// 一局达到这么多分
+    Score(u32),
+    /// 到达这一关
+    ReachLevel(u32),
+    /// 同时满足所有条件
+    All(Vec<AchievementCondition>),
+}
+
+impl AchievementCondition {
+    /// 按本局的进度判断条件是否满足
+    pub fn is_met(&self, progress: &AchievementProgress) -> bool {
+        match self {
+            Self::GhostCombo(count) => progress.combo >= *count,
+            Self::ClearLevelWithoutDying => progress.clears_without_dying > 0,
+            Self::ClearLevelWithoutPowerPellet => progress.clears_without_power_pellet > 0,
+            Self::SurviveChase(secs) => progress.chase_time >= *secs,
+            Self::Score(score) => progress.score >= *score,
+            Self::ReachLevel(level) => progress.level >= *level,
+            Self::All(conditions) => conditions.iter().all(|cond| cond.is_met(progress)),
+        }
+    }
+}
+
+/// 一个成就的定义
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AchievementDef {
+    /// 唯一标识，解锁记录按它保存，发布后不要修改
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: AchievementCondition,
+}
+
+impl AchievementDef {
+    /// 从定义文件读取所有成就，新增成就只需修改文件
+    pub fn load_all(path: &Path) -> anyhow::Result<Vec<Self>> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read achievements {}", path.display()))?;
+        let defs: Vec<Self> = ron::from_str(&text)
+            .with_context(|| format!("Failed to parse achievements {}", path.display()))?;
+
+        let mut ids = HashSet::new();
+        for def in &defs {
+            if !ids.insert(def.id.as_str()) {
+                bail!(
+                    "Duplicate achievement id {:?} in {}",
+                    def.id,
+                    path.display()
+                );
+            }
+        }
+        Ok(defs)
+    }
+}
+
+/// 本局中与成就有关的进度，每局重新开始
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AchievementProgress {
+    /// 当前这颗能量豆已经吃掉的幽灵数
+    pub combo: u32,
+    /// 本关是否从头开始跟踪，从存档继续时无法得知本关之前的情况
+    pub level_tracked: bool,
+    /// 本关失去的生命数
+    pub level_deaths: u32,
+    /// 本关吃掉的能量豆数
+    pub level_power_pellets: u32,
+    /// 本局一次都不死完成的关卡数
+    pub clears_without_dying: u32,
+    /// 本局不吃能量豆完成的关卡数
+    pub clears_without_power_pellet: u32,
+    /// 这条命在追捕模式下存活的时间（秒）
+    pub chase_time: f32,
+    pub score: u32,
+    pub level: u32,
+}
+
+impl AchievementProgress {
+    /// 新的一局
+    ///
+    /// ## Params
+    /// - level_tracked: 是否从关卡开头开始，从存档继续时为 false
+    pub fn new(level_tracked: bool) -> Self {
+        Self {
+            level_tracked,
+            ..Default::default()
+        }
+    }
+
+    /// 记录吃掉一颗豆子
+    pub fn record_pellet(&mut self, pellet_type: PelletType) {
+        if pellet_type == PelletType::Power {
+            self.level_power_pellets += 1;
+        }
+    }
+
+    /// 记录失去一条命
+    pub fn record_death(&mut self) {
+        self.level_deaths += 1;
+        self.chase_time = 0.0;
+    }
+
+    /// 记录完成一关，下一关从头开始跟踪
+    pub fn record_level_cleared(&mut self) {
+        if self.level_tracked {
+            if self.level_deaths == 0 {
+                self.clears_without_dying += 1;
+            }
+            if self.level_power_pellets == 0 {
+                self.clears_without_power_pellet += 1;
+            }
+        }
+        self.level_tracked = true;
+        self.level_deaths = 0;
+        self.level_power_pellets = 0;
+    }
+
+    /// 每个模拟帧之后同步模拟中的状态
+    ///
+    /// ## Params
+    /// - delta_secs: 这一帧的时长
+    /// - chasing: 幽灵是否在追捕玩家
+    /// - combo: 当前这颗能量豆已经吃掉的幽灵数
+    pub fn record_tick(
+        &mut self,
+        delta_secs: f32,
+        chasing: bool,
+        combo: u32,
+        score: u32,
+        level: u32,
+    ) {
+        if chasing {
+            self.chase_time += delta_secs;
+        }
+        self.combo = combo;
+        self.score = score;
+        self.level = level;
+    }
+}
+
+/// 已解锁的成就，保存在档案目录中
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AchievementUnlocks {
+    /// 成就 id 对应的解锁时间（Unix 秒）
+    pub unlocked: BTreeMap<String, u64>,
+}
+
+impl AchievementUnlocks {
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains_key(id)
+    }
+
+    /// 解锁记录文件
+    pub fn path(profile_dir: &Path) -> PathBuf {
+        profile_dir.join(ACHIEVEMENT_UNLOCKS_FILE)
+    }
+
+    /// 从文件读取解锁记录
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read achievements {}", path.display()))?;
+        ron::from_str(&text)
+            .with_context(|| format!("Failed to parse achievements {}", path.display()))
+    }
+
+    /// 保存到文件，目录不存在时自动创建
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, text)
+            .with_context(|| format!("Failed to write achievements {}", path.display()))
+    }
+}
+
+/// 所有成就的定义和解锁记录
+#[derive(Resource, Default)]
+pub struct Achievements {
+    pub defs: Vec<AchievementDef>,
+    pub unlocks: AchievementUnlocks,
+}
+
+impl Achievements {
+    /// 解锁所有条件已经满足的成就
+    ///
+    /// ## Params
+    /// - now: 解锁时间（Unix 秒）
+    ///
+    /// ## Return
+    /// 这次新解锁的成就
+    pub fn unlock_met(&mut self, progress: &AchievementProgress, now: u64) -> Vec<AchievementDef> {
+        let unlocked: Vec<AchievementDef> = self
+            .defs
+            .iter()
+            .filter(|def| !self.unlocks.is_unlocked(&def.id) && def.condition.is_met(progress))
+            .cloned()
+            .collect();
+        for def in &unlocked {
+            self.unlocks.unlocked.insert(def.id.clone(), now);
+        }
+        unlocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn achievements(conditions: &str) -> Achievements {
+        let defs = ron::from_str(conditions).unwrap();
+        Achievements {
+            defs,
+            unlocks: AchievementUnlocks::default(),
+        }
+    }
+
+    #[test]
+    fn test_achievement_conditions() {
+        let mut achievements = achievements(
+            r#"[
+                (id: "combo", name: "", description: "", condition: GhostCombo(4)),
+                (id: "clean", name: "", description: "", condition: ClearLevelWithoutDying),
+                (id: "chase", name: "", description: "", condition: SurviveChase(60.0)),
+                (id: "both", name: "", description: "",
+                    condition: All([ClearLevelWithoutPowerPellet, ReachLevel(2)])),
+            ]"#,
+        );
+        let mut progress = AchievementProgress::new(true);
+
+        // 中途死亡后追捕计时从零开始
+        progress.record_tick(59.0, true, 0, 0, 1);
+        progress.record_death();
+        progress.record_tick(59.0, true, 0, 0, 1);
+        progress.record_pellet(PelletType::Power);
+        progress.record_tick(0.0, false, 4, 0, 1);
+        let ids: Vec<String> = achievements
+            .unlock_met(&progress, 1)
+            .into_iter()
+            .map(|def| def.id)
+            .collect();
+        assert_eq!(ids, vec!["combo"]);
+
+        // 死过一次、吃过能量豆的一关不算，下一关重新开始
+        progress.record_level_cleared();
+        progress.record_tick(1.0, true, 0, 0, 2);
+        let ids: Vec<String> = achievements
+            .unlock_met(&progress, 2)
+            .into_iter()
+            .map(|def| def.id)
+            .collect();
+        assert_eq!(ids, vec!["chase"]);
+        progress.record_level_cleared();
+        let ids: Vec<String> = achievements
+            .unlock_met(&progress, 3)
+            .into_iter()
+            .map(|def| def.id)
+            .collect();
+        assert_eq!(ids, vec!["clean", "both"]);
+        assert_eq!(achievements.unlocks.unlocked.get("combo"), Some(&1));
+    }
+
+    #[test]
+    fn test_untracked_level_does_not_count() {
+        let mut progress = AchievementProgress::new(false);
+        progress.record_level_cleared();
+        assert_eq!(progress.clears_without_dying, 0);
+        progress.record_level_cleared();
+        assert_eq!(progress.clears_without_dying, 1);
+    }
+
+    #[test]
+    fn test_builtin_achievements_load() -> anyhow::Result<()> {
+        let defs = AchievementDef::load_all(Path::new(crate::ACHIEVEMENTS_PATH))?;
+        assert!(!defs.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+use crate::DATA_DIR_NAME;
+
+/// 用户数据目录，保存玩家档案等跨版本保留的数据
+///
+/// Linux 上为 ~/.local/share/pac-man，找不到时退回到当前目录
+pub fn user_data_dir() -> PathBuf {
+    dirs::data_dir().map_or_else(|| PathBuf::from("."), |dir| dir.join(DATA_DIR_NAME))
+}
@@ -1,7 +1,7 @@
 use bevy::ecs::message::Message;
 use glam::IVec2;
 
-use crate::{PelletType, is_power_pellet};
+use crate::{FruitType, GhostType, PelletType, is_power_pellet};
 
 /// 吃豆子事件
 #[derive(Message)]
@@ -24,3 +24,57 @@ impl EatPelletEvent {
         }
     }
 }
+
+/// 吃水果事件
+#[derive(Message)]
+pub struct FruitEatenEvent {
+    /// 水果的坐标
+    pub position: IVec2,
+    /// 水果类型
+    pub fruit_type: FruitType,
+    /// 获得的分数
+    pub points: u32,
+}
+
+/// 吃幽灵事件
+#[derive(Message)]
+pub struct GhostEatenEvent {
+    /// 幽灵被吃掉时的坐标
+    pub position: IVec2,
+    /// 被吃掉的幽灵类型
+    pub ghost_type: GhostType,
+    /// 获得的分数
+    pub points: u32,
+}
+
+/// 玩家死亡事件
+#[derive(Message)]
+pub struct PlayerDeathEvent {
+    /// 玩家死亡时的坐标
+    pub position: IVec2,
+    /// 撞上玩家的幽灵类型
+    pub ghost_type: GhostType,
+}
+
+/// 关卡完成事件
+#[derive(Message)]
+pub struct LevelClearedEvent {
+    /// 刚刚完成的关卡编号
+    pub level: u32,
+}
+
+/// 奖励生命事件
+#[derive(Message)]
+pub struct ExtraLifeEvent {
+    /// 奖励后的生命数
+    pub lives: u32,
+    /// 触发奖励时的分数
+    pub score: u32,
+}
+
+/// 解锁成就事件
+#[derive(Message)]
+pub struct AchievementUnlockedEvent {
+    pub name: String,
+    pub description: String,
+}
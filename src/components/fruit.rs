@@ -0,0 +1,107 @@
+use bevy::{color::Color, ecs::component::Component};
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    APPLE_COLOR, BELL_COLOR, CHERRY_COLOR, FRUIT_DURATION, GALAXIAN_COLOR, KEY_COLOR, MELON_COLOR,
+    ORANGE_COLOR, STRAWBERRY_COLOR,
+};
+
+/// 奖励水果类型，随关卡变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FruitType {
+    Cherry,
+    Strawberry,
+    Orange,
+    Apple,
+    Melon,
+    Galaxian,
+    Bell,
+    Key,
+}
+
+impl FruitType {
+    /// 根据关卡编号获取水果类型（原版吃豆人规则）
+    pub fn for_level(level: u32) -> Self {
+        match level {
+            0..=1 => Self::Cherry,
+            2 => Self::Strawberry,
+            3..=4 => Self::Orange,
+            5..=6 => Self::Apple,
+            7..=8 => Self::Melon,
+            9..=10 => Self::Galaxian,
+            11..=12 => Self::Bell,
+            _ => Self::Key,
+        }
+    }
+
+    /// 吃掉水果获得的分数
+    pub fn points(&self) -> u32 {
+        match self {
+            Self::Cherry => 100,
+            Self::Strawberry => 300,
+            Self::Orange => 500,
+            Self::Apple => 700,
+            Self::Melon => 1000,
+            Self::Galaxian => 2000,
+            Self::Bell => 3000,
+            Self::Key => 5000,
+        }
+    }
+}
+
+impl From<FruitType> for Color {
+    fn from(fruit_type: FruitType) -> Self {
+        match fruit_type {
+            FruitType::Cherry => CHERRY_COLOR,
+            FruitType::Strawberry => STRAWBERRY_COLOR,
+            FruitType::Orange => ORANGE_COLOR,
+            FruitType::Apple => APPLE_COLOR,
+            FruitType::Melon => MELON_COLOR,
+            FruitType::Galaxian => GALAXIAN_COLOR,
+            FruitType::Bell => BELL_COLOR,
+            FruitType::Key => KEY_COLOR,
+        }
+    }
+}
+
+/// 奖励水果
+#[derive(Debug, Clone, Component, Serialize, Deserialize)]
+pub struct Fruit {
+    pub position: IVec2,
+    pub fruit_type: FruitType,
+    /// 剩余存在时间
+    pub remaining_time: f32,
+}
+
+impl Fruit {
+    pub fn new(position: IVec2, level: u32) -> Self {
+        Self {
+            position,
+            fruit_type: FruitType::for_level(level),
+            remaining_time: FRUIT_DURATION,
+        }
+    }
+
+    /// 更新剩余时间
+    ///
+    /// ## Return
+    /// 若水果已经消失则返回 true
+    pub fn update(&mut self, delta_secs: f32) -> bool {
+        self.remaining_time -= delta_secs;
+        self.remaining_time <= 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fruit_for_level() {
+        assert_eq!(FruitType::for_level(1), FruitType::Cherry);
+        assert_eq!(FruitType::for_level(4), FruitType::Orange);
+        assert_eq!(FruitType::for_level(13), FruitType::Key);
+        assert_eq!(FruitType::for_level(100).points(), 5000);
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::ecs::resource::Resource;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::GhostType;
+
+/// 游戏随机数，相同种子下幽灵的随机行为完全相同
+///
+/// 每只幽灵使用独立的随机数流，一只幽灵多用或少用随机数不会影响其他幽灵
+#[derive(Resource, Debug, Clone)]
+pub struct GameRng {
+    /// 随机数种子
+    seed: u64,
+    /// 每只幽灵的随机数流，下标为 GhostType
+    ghosts: [ChaCha12Rng; 4],
+}
+
+/// 随机数流的状态，保存在存档中，继续游戏后的随机行为与没有中断时完全相同
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRngState {
+    pub seed: u64,
+    /// 每只幽灵的随机数流已经用掉的字数
+    pub word_pos: [u64; 4],
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(default_seed())
+    }
+}
+
+/// 命令行或环境变量指定了种子时使用该种子，否则随机生成
+pub fn default_seed() -> u64 {
+    seed_from_args(std::env::args())
+        .or_else(|| std::env::var(SEED_ENV).ok()?.parse().ok())
+        .unwrap_or_else(rand::random)
+}
+
+/// 指定随机数种子的环境变量
+pub const SEED_ENV: &str = "PAC_MAN_SEED";
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ghosts: std::array::from_fn(|i| ghost_stream(seed, i)),
+        }
+    }
+
+    /// 从存档的随机数状态继续
+    pub fn from_state(state: GameRngState) -> Self {
+        let mut rng = Self::new(state.seed);
+        for (stream, word_pos) in rng.ghosts.iter_mut().zip(state.word_pos) {
+            stream.set_word_pos(u128::from(word_pos));
+        }
+        rng
+    }
+
+    /// 当前随机数状态
+    pub fn state(&self) -> GameRngState {
+        GameRngState {
+            seed: self.seed,
+            word_pos: std::array::from_fn(|i| self.ghosts[i].get_word_pos() as u64),
+        }
+    }
+
+    /// 随机数种子
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// 用同一个种子从头开始
+    pub fn restart(&mut self) {
+        *self = Self::new(self.seed);
+    }
+
+    /// 指定幽灵的随机数流
+    pub fn ghost(&mut self, ghost_type: GhostType) -> &mut ChaCha12Rng {
+        &mut self.ghosts[ghost_type as usize]
+    }
+}
+
+/// 第 index 只幽灵的随机数流
+///
+/// 种子和幽灵下标一起组成 ChaCha 的密钥，不同种子、不同幽灵的随机数流互不重叠
+fn ghost_stream(seed: u64, index: usize) -> ChaCha12Rng {
+    let mut key = [0u8; 32];
+    key[..8].copy_from_slice(&seed.to_le_bytes());
+    key[8..16].copy_from_slice(&(index as u64).to_le_bytes());
+    ChaCha12Rng::from_seed(key)
+}
+
+/// 从命令行参数中读取种子，支持 `--seed 42` 和 `--seed=42`
+///
+/// ## Return
+/// 没有指定或格式错误时返回 None
+pub fn seed_from_args(args: impl IntoIterator<Item = String>) -> Option<u64> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--seed" {
+            return args.next()?.parse().ok();
+        }
+        if let Some(value) = arg.strip_prefix("--seed=") {
+            return value.parse().ok();
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_streams() {
+        let mut a = GameRng::new(42);
+        let mut b = GameRng::new(42);
+        let rolls = |rng: &mut GameRng, ghost_type| -> Vec<u32> {
+            (0..8).map(|_| rng.ghost(ghost_type).random()).collect()
+        };
+
+        // 只用 Blinky 的随机数不影响 Pinky
+        rolls(&mut a, GhostType::Blinky);
+        assert_eq!(
+            rolls(&mut a, GhostType::Pinky),
+            rolls(&mut b, GhostType::Pinky)
+        );
+        assert_ne!(
+            rolls(&mut a, GhostType::Inky),
+            rolls(&mut a, GhostType::Clyde)
+        );
+
+        a.restart();
+        let mut c = GameRng::new(42);
+        assert_eq!(
+            rolls(&mut a, GhostType::Blinky),
+            rolls(&mut c, GhostType::Blinky)
+        );
+    }
+
+    #[test]
+    fn test_adjacent_seeds_do_not_share_streams() {
+        let mut a = GameRng::new(7);
+        let mut b = GameRng::new(8);
+        let first: u64 = a.ghost(GhostType::Pinky).random();
+        let second: u64 = b.ghost(GhostType::Blinky).random();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_seed_from_args() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(seed_from_args(args(&["pac-man", "--seed", "7"])), Some(7));
+        assert_eq!(seed_from_args(args(&["pac-man", "--seed=8"])), Some(8));
+        assert_eq!(seed_from_args(args(&["pac-man"])), None);
+        assert_eq!(seed_from_args(args(&["pac-man", "--seed", "x"])), None);
+    }
+}
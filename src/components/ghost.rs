@@ -1,14 +1,16 @@
 use bevy::{color::Color, ecs::component::Component};
-use glam::IVec2;
+use glam::{IVec2, Vec2};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::collections::{BinaryHeap, HashMap};
 
 use crate::{
-    BLINKY_COLOR, CLYDE_COLOR, INKY_COLOR, MapData, Movement, PINKY_COLOR, TryMove, check_position,
+    BLINKY_COLOR, CLYDE_COLOR, GHOST_RETURN_SPEED, INKY_COLOR, LevelSpec, MapData, Movement,
+    PINKY_COLOR, TryMove,
 };
 
 /// 幽灵移动状态
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GhostMode {
     /// 追捕模式: 正常追捕玩家
     Chase,
@@ -21,7 +23,7 @@ pub enum GhostMode {
 }
 
 /// 幽灵类型
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum GhostType {
     Blinky, // 红色: 激进追捕
     Pinky,  // 粉色: 预判拦截
@@ -29,6 +31,16 @@ pub enum GhostType {
     Clyde,  // 橙色: 距离近时逃脱，距离远时追逐
 }
 
+impl GhostType {
+    /// 所有幽灵类型
+    pub const ALL: [GhostType; 4] = [
+        GhostType::Blinky,
+        GhostType::Pinky,
+        GhostType::Inky,
+        GhostType::Clyde,
+    ];
+}
+
 /// A*算法节点
 #[derive(Debug, Clone, PartialEq)]
 struct Node {
@@ -41,8 +53,8 @@ impl Eq for Node {}
 
 impl PartialOrd for Node {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        // 最小堆，所以比较顺序反过来
-        other.f_cost.partial_cmp(&self.f_cost)
+        // 最小堆，比较顺序在 Ord 中反过来
+        Some(self.cmp(other))
     }
 }
 
@@ -53,10 +65,12 @@ impl Ord for Node {
 }
 
 /// 幽灵
-#[derive(Debug, Component)]
+#[derive(Debug, Clone, Component, Serialize, Deserialize)]
 pub struct Ghost {
     /// 当前地图格坐标
     pub tile_pos: IVec2,
+    /// 本帧移动前的地图格坐标
+    pub prev_tile_pos: IVec2,
     /// 幽灵类型
     pub ghost_type: GhostType,
     /// 初始重生位置
@@ -67,23 +81,22 @@ pub struct Ghost {
     pub mode: GhostMode,
     /// 恐惧状态剩余时间
     pub frightened_time: f32,
-    /// 分散模式计时器
-    pub scatter_timer: f32,
-    /// 是否在基地中
+    /// 恐惧状态结束前的闪烁次数
+    pub frightened_flashes: u32,
+    /// 是否在基地中等待离开
     pub in_house: bool,
-    /// 离开基地的延迟
-    pub house_delay: f32,
+    /// 正在离开基地时的出口位置
+    pub house_exit: Option<IVec2>,
     /// 移动组件
     pub movement: Movement,
     /// 是否在交叉路口需要重新选择方向
     pub at_intersection: bool,
     /// 上次移动方向
     pub last_move_dir: IVec2,
-    /// 模式切换计时器
-    pub mode_switch_timer: f32,
-    /// 当前模式持续时间
-    pub current_mode_duration: f32,
-    /// 路径缓存
+    /// 模式切换后下一次选择方向时强制回头
+    pub reverse_pending: bool,
+    /// 路径缓存，不保存到存档中
+    #[serde(skip)]
     path_cache: Option<Vec<IVec2>>,
 }
 
@@ -91,58 +104,99 @@ impl Ghost {
     pub fn new(x: i32, y: i32, ghost_type: GhostType) -> Self {
         Self {
             tile_pos: IVec2::new(x, y),
+            prev_tile_pos: IVec2::new(x, y),
             ghost_type,
             spawn_pos: IVec2::new(x, y),
             target_pos: IVec2::new(x, y),
             mode: GhostMode::Scatter,
             frightened_time: 0.0,
-            scatter_timer: 0.0,
+            frightened_flashes: 0,
             in_house: true,
-            house_delay: match ghost_type {
-                GhostType::Blinky => 0.0,
-                GhostType::Pinky => 5.0,
-                GhostType::Inky => 10.0,
-                GhostType::Clyde => 15.0,
-            },
-            movement: Movement::new(4.0, true),
+            house_exit: None,
+            movement: Movement::new(LevelSpec::for_level(1).ghost_speed(), true),
             at_intersection: false,
             last_move_dir: IVec2::ZERO,
-            mode_switch_timer: 0.0,
-            current_mode_duration: 7.0, // 初始分散模式7秒
+            reverse_pending: false,
             path_cache: None,
         }
     }
 
-    pub fn set_frightened(&mut self, duration: f32) {
-        if self.mode != GhostMode::ReturnToBase {
+    /// 回到重生点，恢复初始状态
+    pub fn reset(&mut self) {
+        *self = Self::new(self.spawn_pos.x, self.spawn_pos.y, self.ghost_type);
+    }
+
+    /// 进入恐惧状态，持续时间和速度由关卡参数决定
+    pub fn set_frightened(&mut self, spec: &LevelSpec) {
+        // 即使恐惧时间为 0，吃到能量豆也会让幽灵回头
+        self.request_reverse();
+
+        // 高关卡恐惧时间为 0，幽灵不再变蓝
+        if self.mode != GhostMode::ReturnToBase && spec.frightened_time > 0.0 {
             self.mode = GhostMode::Frightened;
-            self.frightened_time = duration;
-            // 恐惧状态下速度减半，但方向保持不变
-            self.movement.speed = 2.0;
+            self.frightened_time = spec.frightened_time;
+            self.frightened_flashes = spec.frightened_flashes;
+            // 恐惧状态下减速，但方向保持不变
+            self.movement.set_speed(spec.ghost_frightened_speed());
             // 清除路径缓存
             self.path_cache = None;
         }
     }
 
+    /// 请求强制回头，在基地中或返回基地时忽略
+    fn request_reverse(&mut self) {
+        if !self.in_house && self.house_exit.is_none() && self.mode != GhostMode::ReturnToBase {
+            self.reverse_pending = true;
+        }
+    }
+
     /// 被吃掉，返回基地
     pub fn eaten(&mut self) {
         self.mode = GhostMode::ReturnToBase;
         // 注意：这里不立即重置位置，让幽灵自己移动回基地
         // 返回基地时高速移动
-        self.movement.speed = 8.0;
+        self.movement.set_speed(GHOST_RETURN_SPEED);
         // 清除路径缓存，重新计算回基地的路径
         self.path_cache = None;
     }
 
-    /// 离开基地
-    pub fn try_leave_house(&mut self) {
-        if self.in_house && self.house_delay <= 0.0 {
-            self.in_house = false;
+    /// 离开基地，沿路径走到基地门外
+    pub fn leave_house(&mut self, map_data: &MapData) {
+        if !self.in_house {
+            return;
+        }
+
+        self.in_house = false;
+        self.house_exit = map_data.house_exit(self.tile_pos);
+        if self.mode != GhostMode::Frightened {
             self.mode = GhostMode::Scatter;
-            self.movement.speed = 4.0; // 恢复正常速度
-            self.mode_switch_timer = 0.0;
-            self.current_mode_duration = 7.0; // 分散模式7秒
         }
+        self.path_cache = None;
+    }
+
+    /// 连续坐标（单位：格）
+    ///
+    /// ## Params
+    /// - map_data: 地图数据
+    /// - overstep_secs: 距离上一个模拟帧已经过的时间，渲染插值时使用，模拟中为 0
+    pub fn position(&self, map_data: &MapData, overstep_secs: f32) -> Vec2 {
+        let advancing = self
+            .neighbor(map_data, self.tile_pos, self.movement.direction)
+            .is_some();
+        self.movement
+            .position(self.tile_pos, advancing, overstep_secs)
+    }
+
+    /// 是否可以通过基地门：只有进出基地时可以
+    fn can_pass_door(&self) -> bool {
+        self.in_house || self.house_exit.is_some() || self.mode == GhostMode::ReturnToBase
+    }
+
+    /// 从 pos 沿 dir 走一格后的位置，支持隧道，离开或返回基地时才能通过基地门
+    fn neighbor(&self, map_data: &MapData, pos: IVec2, dir: IVec2) -> Option<IVec2> {
+        map_data.neighbor(pos, dir).filter(|next| {
+            self.can_pass_door() || !map_data.is_house_door(next.x as usize, next.y as usize)
+        })
     }
 
     /// A*路径查找算法
@@ -156,8 +210,8 @@ impl Ghost {
         let mut g_score = HashMap::new();
         let mut f_score = HashMap::new();
 
-        // 启发式函数：曼哈顿距离
-        let heuristic = |a: IVec2, b: IVec2| -> i32 { (a.x - b.x).abs() + (a.y - b.y).abs() };
+        // 启发式函数：考虑隧道折返的曼哈顿距离
+        let heuristic = |a: IVec2, b: IVec2| -> i32 { map_data.wrapped_distance(a, b) };
 
         g_score.insert(start, 0);
         f_score.insert(start, heuristic(start, target));
@@ -182,21 +236,17 @@ impl Ghost {
                 return Some(path);
             }
 
-            let neighbors = vec![
-                current.position + IVec2::new(1, 0),
-                current.position + IVec2::new(-1, 0),
-                current.position + IVec2::new(0, 1),
-                current.position + IVec2::new(0, -1),
-            ];
+            // 跳过墙壁，越过地图边缘时经隧道折返
+            let neighbors = [
+                IVec2::new(1, 0),
+                IVec2::new(-1, 0),
+                IVec2::new(0, 1),
+                IVec2::new(0, -1),
+            ]
+            .into_iter()
+            .filter_map(|dir| self.neighbor(map_data, current.position, dir));
 
             for neighbor in neighbors {
-                // 检查边界和墙壁
-                if !check_position(neighbor.x, neighbor.y, map_data.width, map_data.height)
-                    || map_data.is_wall(neighbor.x as usize, neighbor.y as usize)
-                {
-                    continue;
-                }
-
                 let tentative_g_score = g_score[&current.position] + 1;
 
                 if !g_score.contains_key(&neighbor) || tentative_g_score < g_score[&neighbor] {
@@ -329,9 +379,7 @@ impl Ghost {
                     return false;
                 }
 
-                let new_pos = self.tile_pos + dir;
-                check_position(new_pos.x, new_pos.y, map_data.width, map_data.height)
-                    && !map_data.is_wall(new_pos.x as usize, new_pos.y as usize)
+                self.neighbor(map_data, self.tile_pos, dir).is_some()
             })
             .collect()
     }
@@ -379,15 +427,14 @@ impl Ghost {
                 self.path_cache = self.find_path(self.tile_pos, target_pos, map_data);
             }
 
-            if let Some(ref path) = self.path_cache {
-                if path.len() > 1 {
-                    let next_pos = path[1]; // 路径中的下一个位置
-                    let direction = next_pos - self.tile_pos;
+            if let Some(ref path) = self.path_cache
+                && path.len() > 1
+            {
+                let next_pos = path[1]; // 路径中的下一个位置
 
-                    // 如果这个方向是可行的，就选择它
-                    if possible_dirs.contains(&direction) {
-                        return Some(direction);
-                    }
+                // 如果这个方向是可行的，就选择它
+                if let Some(direction) = self.direction_to(next_pos, possible_dirs, map_data) {
+                    return Some(direction);
                 }
             }
 
@@ -402,17 +449,15 @@ impl Ghost {
         }
 
         // 对于追逐和分散模式，只在复杂情况下使用A*
-        if possible_dirs.len() > 2 {
-            // 使用A*找到最佳路径
-            if let Some(path) = self.find_path(self.tile_pos, target_pos, map_data) {
-                if path.len() > 1 {
-                    let next_pos = path[1];
-                    let direction = next_pos - self.tile_pos;
-
-                    if possible_dirs.contains(&direction) {
-                        return Some(direction);
-                    }
-                }
+        // 使用A*找到最佳路径
+        if possible_dirs.len() > 2
+            && let Some(path) = self.find_path(self.tile_pos, target_pos, map_data)
+            && path.len() > 1
+        {
+            let next_pos = path[1];
+
+            if let Some(direction) = self.direction_to(next_pos, possible_dirs, map_data) {
+                return Some(direction);
             }
         }
 
@@ -428,88 +473,64 @@ impl Ghost {
             .copied()
     }
 
-    /// 更新状态计时器
-    pub fn update_timers(&mut self, delta_secs: f32) {
-        // 更新离开基地的延迟
-        if self.in_house {
-            self.house_delay -= delta_secs;
-            self.try_leave_house();
-            return; // 在基地内不更新其他计时器
+    /// 找出能一步走到相邻格 next_pos 的方向（经隧道折返时坐标差不等于方向）
+    fn direction_to(
+        &self,
+        next_pos: IVec2,
+        possible_dirs: &[IVec2],
+        map_data: &MapData,
+    ) -> Option<IVec2> {
+        possible_dirs
+            .iter()
+            .find(|&&dir| self.neighbor(map_data, self.tile_pos, dir) == Some(next_pos))
+            .copied()
+    }
+
+    /// 当前状态下的基础速度（格/秒）
+    fn base_speed(&self, spec: &LevelSpec) -> f32 {
+        match self.mode {
+            GhostMode::ReturnToBase => GHOST_RETURN_SPEED,
+            GhostMode::Frightened => spec.ghost_frightened_speed(),
+            GhostMode::Chase | GhostMode::Scatter => spec.ghost_speed(),
         }
+    }
 
-        // 更新模式切换计时器
-        self.mode_switch_timer += delta_secs;
+    /// 更新状态计时器
+    ///
+    /// ## Params
+    /// - delta_secs: 经过的时间
+    /// - spec: 当前关卡参数
+    /// - scheduled: 全局时间表当前的分散/追捕模式
+    pub fn update_timers(&mut self, delta_secs: f32, spec: &LevelSpec, scheduled: GhostMode) {
+        // 在基地内等待时只有恐惧状态照常计时，何时离开由 GhostHouse 决定
+        if self.in_house && self.mode != GhostMode::Frightened {
+            return;
+        }
 
         match self.mode {
             GhostMode::Frightened => {
                 self.frightened_time -= delta_secs;
                 if self.frightened_time <= 0.0 {
-                    // 恐惧状态结束，恢复到之前的模式
-                    self.mode = GhostMode::Chase;
-                    self.movement.speed = 4.0;
-                    self.mode_switch_timer = 0.0;
-                    self.current_mode_duration = 20.0; // 追逐模式20秒
+                    // 恐惧状态结束，回到时间表当前的模式
+                    self.mode = scheduled;
                     // 清除路径缓存
                     self.path_cache = None;
                 }
             }
             GhostMode::Scatter | GhostMode::Chase => {
-                // 原版吃豆人的模式切换序列: 散射7秒 → 追逐20秒 → 散射7秒 → 追逐20秒 → 散射5秒 → 追逐20秒 → 散射5秒 → 永久追逐
-                if self.mode_switch_timer >= self.current_mode_duration {
-                    let old_mode = self.mode;
-                    self.mode = if self.mode == GhostMode::Scatter {
-                        GhostMode::Chase
-                    } else {
-                        GhostMode::Scatter
-                    };
-
-                    self.mode_switch_timer = 0.0;
-
-                    // 只在模式真正改变时清除路径缓存
-                    if old_mode != self.mode {
-                        self.path_cache = None;
-                    }
-
-                    // 更新下一个模式的持续时间
-                    self.current_mode_duration = match self.mode {
-                        GhostMode::Scatter => {
-                            // 散射模式持续时间递减
-                            if self.scatter_timer < 14.0 {
-                                // 第一次散射7秒，第二次散射7秒
-                                7.0
-                            } else if self.scatter_timer < 19.0 {
-                                // 第三次散射5秒
-                                5.0
-                            } else {
-                                // 第四次散射5秒后永久追逐
-                                5.0
-                            }
-                        }
-                        GhostMode::Chase => {
-                            // 追逐模式固定20秒，直到第四次散射后变为永久追逐
-                            if self.scatter_timer >= 24.0 {
-                                f32::INFINITY // 永久追逐
-                            } else {
-                                20.0
-                            }
-                        }
-                        _ => 20.0,
-                    };
-
-                    self.scatter_timer += self.current_mode_duration;
+                // 跟随全局时间表切换模式，每次切换都要回头
+                if self.mode != scheduled {
+                    self.mode = scheduled;
+                    self.path_cache = None;
+                    self.request_reverse();
                 }
             }
             GhostMode::ReturnToBase => {
-                // 回到基地后恢复正常
-                if self.tile_pos == self.spawn_pos {
-                    self.mode = GhostMode::Chase;
-                    self.movement.speed = 4.0;
-                    self.mode_switch_timer = 0.0;
-                    self.current_mode_duration = 20.0;
-                    self.path_cache = None;
-                }
+                // 到达基地后的处理需要地图数据，在 update 中进行
             }
         }
+
+        self.movement.set_speed(self.base_speed(spec));
     }
 
     /// 更新幽灵状态
@@ -520,9 +541,53 @@ impl Ghost {
         blinky_pos: IVec2,
         map_data: &MapData,
         rng: &mut impl Rng,
-        delta_secs: f32,
+        spec: &LevelSpec,
     ) {
-        self.update_timers(delta_secs);
+        // 回到基地后恢复正常，并马上沿出口路径重新出发
+        if self.mode == GhostMode::ReturnToBase && self.tile_pos == self.spawn_pos {
+            self.revive(map_data);
+        }
+
+        // 在基地内原地等待
+        if self.in_house {
+            self.movement.direction = IVec2::ZERO;
+            return;
+        }
+
+        // 隧道中减速
+        if self.mode != GhostMode::ReturnToBase && map_data.is_tunnel(self.tile_pos) {
+            self.movement
+                .set_speed(self.movement.speed.min(spec.ghost_tunnel_speed()));
+        }
+
+        // 正在离开基地：沿路径走向出口，不受不能回头的限制
+        if let Some(exit) = self.house_exit {
+            self.move_to_house_exit(exit, map_data);
+            return;
+        }
+
+        // 模式切换后立即回头，只覆盖这一次不能回头的限制
+        if std::mem::take(&mut self.reverse_pending) {
+            // 走到一半时从正在前往的格子往回走，画面不会跳动
+            if self.movement.direction != IVec2::ZERO
+                && self.movement.accumulated_time > 0.0
+                && let Some(next) = self.neighbor(map_data, self.tile_pos, self.movement.direction)
+            {
+                self.tile_pos = next;
+                self.movement.reverse();
+                self.last_move_dir = self.movement.direction;
+                self.path_cache = None;
+                return;
+            }
+
+            let back = -self.last_move_dir;
+            if back != IVec2::ZERO && self.neighbor(map_data, self.tile_pos, back).is_some() {
+                self.movement.direction = back;
+                self.last_move_dir = back;
+                self.path_cache = None;
+                return;
+            }
+        }
 
         // 只在交叉路口或需要时重新计算方向
         self.at_intersection = self.check_intersection(map_data);
@@ -556,15 +621,42 @@ impl Ghost {
         }
     }
 
+    /// 被吃掉后回到基地，恢复正常并准备离开
+    fn revive(&mut self, map_data: &MapData) {
+        // 下一帧由全局时间表决定分散还是追捕
+        self.mode = GhostMode::Chase;
+        self.path_cache = None;
+        self.house_exit = map_data.house_exit(self.spawn_pos);
+    }
+
+    /// 沿 A* 路径走向基地出口，到达后恢复正常行动
+    fn move_to_house_exit(&mut self, exit: IVec2, map_data: &MapData) {
+        if self.tile_pos == exit {
+            self.house_exit = None;
+            self.path_cache = None;
+            return;
+        }
+
+        let possible_dirs = self.get_possible_direction(map_data, true);
+        let next_dir = self
+            .find_path(self.tile_pos, exit, map_data)
+            .and_then(|path| path.get(1).copied())
+            .and_then(|next_pos| self.direction_to(next_pos, &possible_dirs, map_data));
+
+        if let Some(dir) = next_dir {
+            self.movement.direction = dir;
+            self.last_move_dir = dir;
+        }
+    }
+
     /// 检查是否会撞墙
     fn will_hit_wall(&self, map_data: &MapData) -> bool {
         if self.movement.direction == IVec2::ZERO {
             return false;
         }
 
-        let next_pos = self.tile_pos + self.movement.direction;
-        !check_position(next_pos.x, next_pos.y, map_data.width, map_data.height)
-            || map_data.is_wall(next_pos.x as usize, next_pos.y as usize)
+        self.neighbor(map_data, self.tile_pos, self.movement.direction)
+            .is_none()
     }
 }
 
@@ -574,14 +666,7 @@ impl TryMove for Ghost {
             return None;
         }
 
-        let new_pos = self.tile_pos + self.movement.direction;
-        if !map_data.is_valid_position(new_pos.x, new_pos.y)
-            || map_data.is_wall(new_pos.x as usize, new_pos.y as usize)
-        {
-            return None;
-        }
-
-        Some(new_pos)
+        self.neighbor(map_data, self.tile_pos, self.movement.direction)
     }
 }
 
@@ -595,3 +680,50 @@ impl From<GhostType> for Color {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{GameRng, MAP_PATH, MapLoader, TextMapLoader};
+
+    #[test]
+    fn test_reverse_on_mode_change() -> anyhow::Result<()> {
+        let map_data = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let spec = LevelSpec::for_level(1);
+        let mut rng = GameRng::new(0);
+
+        let mut ghost = Ghost::new(3, 5, GhostType::Blinky);
+        ghost.in_house = false;
+        ghost.movement.direction = IVec2::X;
+        ghost.last_move_dir = IVec2::X;
+
+        // 分散切换到追捕，立即回头
+        ghost.update_timers(0.1, spec, GhostMode::Chase);
+        assert!(ghost.reverse_pending);
+        ghost.update(
+            IVec2::new(26, 29),
+            IVec2::ZERO,
+            IVec2::ZERO,
+            &map_data,
+            rng.ghost(GhostType::Blinky),
+            spec,
+        );
+        assert_eq!(ghost.movement.direction, IVec2::NEG_X);
+        assert!(!ghost.reverse_pending);
+
+        // 进入恐惧状态同样回头
+        ghost.set_frightened(spec);
+        ghost.update(
+            IVec2::new(26, 29),
+            IVec2::ZERO,
+            IVec2::ZERO,
+            &map_data,
+            rng.ghost(GhostType::Blinky),
+            spec,
+        );
+        assert_eq!(ghost.movement.direction, IVec2::X);
+        Ok(())
+    }
+}
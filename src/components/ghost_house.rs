@@ -0,0 +1,147 @@
+use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{GHOST_HOUSE_GLOBAL_DOT_LIMITS, GHOST_HOUSE_IDLE_LIMIT, GhostType};
+
+/// 幽灵基地，按原版吃豆人的豆子计数规则决定幽灵何时离开
+///
+/// - 平时只有优先离开的幽灵（按 Blinky、Pinky、Inky、Clyde 顺序第一个在基地中的）累计个人豆子数
+/// - 失去生命后改用全局豆子计数，直到 Clyde 因此离开
+/// - 一段时间没吃豆子会强制放出优先离开的幽灵
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct GhostHouse {
+    /// 当前关卡，决定个人豆子数上限
+    pub level: u32,
+    /// 每只幽灵的个人豆子计数，下标为 GhostType
+    pub dot_counters: [u32; 4],
+    /// 全局豆子计数，失去生命后启用
+    pub global_counter: Option<u32>,
+    /// 距离上次吃豆子的时间
+    pub idle_timer: f32,
+}
+
+impl Default for GhostHouse {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl GhostHouse {
+    pub fn new(level: u32) -> Self {
+        Self {
+            level,
+            dot_counters: [0; 4],
+            global_counter: None,
+            idle_timer: 0.0,
+        }
+    }
+
+    /// 个人豆子数上限
+    pub fn personal_limit(ghost_type: GhostType, level: u32) -> u32 {
+        match (ghost_type, level) {
+            (GhostType::Inky, 1) => 30,
+            (GhostType::Clyde, 1) => 60,
+            (GhostType::Clyde, 2) => 50,
+            _ => 0,
+        }
+    }
+
+    /// 吃掉一颗豆子
+    ///
+    /// ## Params
+    /// - waiting: 当前优先离开的幽灵，基地为空时为 None
+    pub fn on_pellet_eaten(&mut self, waiting: Option<GhostType>) {
+        self.idle_timer = 0.0;
+        match (self.global_counter.as_mut(), waiting) {
+            (Some(counter), _) => *counter += 1,
+            (None, Some(ghost_type)) => self.dot_counters[ghost_type as usize] += 1,
+            (None, None) => {}
+        }
+    }
+
+    /// 失去生命后启用全局豆子计数
+    pub fn on_life_lost(&mut self) {
+        self.global_counter = Some(0);
+        self.idle_timer = 0.0;
+    }
+
+    /// 更新没吃豆子的计时
+    pub fn tick(&mut self, delta_secs: f32) {
+        self.idle_timer += delta_secs;
+    }
+
+    /// 判断优先离开的幽灵此刻是否应该离开基地
+    pub fn should_release(&mut self, ghost_type: GhostType) -> bool {
+        if self.idle_timer >= GHOST_HOUSE_IDLE_LIMIT {
+            self.idle_timer = 0.0;
+            return true;
+        }
+
+        match self.global_counter {
+            Some(counter) => {
+                if counter < GHOST_HOUSE_GLOBAL_DOT_LIMITS[ghost_type as usize] {
+                    return false;
+                }
+                // Clyde 按全局计数离开后恢复个人计数
+                if ghost_type == GhostType::Clyde {
+                    self.global_counter = None;
+                }
+                true
+            }
+            None => {
+                self.dot_counters[ghost_type as usize]
+                    >= Self::personal_limit(ghost_type, self.level)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_personal_dot_counter() {
+        let mut house = GhostHouse::new(1);
+        assert!(house.should_release(GhostType::Pinky));
+
+        // 第一关 Inky 需要 30 颗豆子
+        for _ in 0..29 {
+            house.on_pellet_eaten(Some(GhostType::Inky));
+        }
+        assert!(!house.should_release(GhostType::Inky));
+        house.on_pellet_eaten(Some(GhostType::Inky));
+        assert!(house.should_release(GhostType::Inky));
+
+        // 第三关之后全部立即离开
+        assert!(GhostHouse::new(3).should_release(GhostType::Clyde));
+    }
+
+    #[test]
+    fn test_global_dot_counter() {
+        let mut house = GhostHouse::new(1);
+        house.on_life_lost();
+        for _ in 0..7 {
+            house.on_pellet_eaten(Some(GhostType::Pinky));
+        }
+        assert!(house.should_release(GhostType::Pinky));
+        assert!(!house.should_release(GhostType::Inky));
+
+        for _ in 7..32 {
+            house.on_pellet_eaten(Some(GhostType::Inky));
+        }
+        assert!(house.should_release(GhostType::Clyde));
+        // Clyde 离开后全局计数停用
+        assert_eq!(house.global_counter, None);
+    }
+
+    #[test]
+    fn test_idle_timer_release() {
+        let mut house = GhostHouse::new(1);
+        house.tick(GHOST_HOUSE_IDLE_LIMIT - 0.1);
+        assert!(!house.should_release(GhostType::Clyde));
+        house.tick(0.2);
+        assert!(house.should_release(GhostType::Clyde));
+        assert_eq!(house.idle_timer, 0.0);
+    }
+}
@@ -0,0 +1,115 @@
+use bevy::ecs::resource::Resource;
+
+use crate::GhostMode;
+
+/// 第 1 关的分散/追捕时长：散射7秒 → 追逐20秒 → 散射7秒 → 追逐20秒 → 散射5秒 → 追逐20秒 → 散射5秒 → 永久追逐
+const SCHEDULE_1: [f32; 7] = [7.0, 20.0, 7.0, 20.0, 5.0, 20.0, 5.0];
+
+/// 第 2 ~ 4 关：第三次追逐几乎持续整关，最后一次散射只有一帧
+const SCHEDULE_2_4: [f32; 7] = [7.0, 20.0, 7.0, 20.0, 5.0, 1033.0, 1.0 / 60.0];
+
+/// 第 5 关及之后：散射时间缩短为 5 秒
+const SCHEDULE_5: [f32; 7] = [5.0, 20.0, 5.0, 20.0, 5.0, 1037.0, 1.0 / 60.0];
+
+/// 所有幽灵共用的分散/追捕模式时间表
+///
+/// 偶数阶段为分散模式，奇数阶段为追捕模式，表格之后永久追捕。
+/// 有幽灵处于恐惧状态时时间表暂停。
+#[derive(Resource, Debug, Clone)]
+pub struct GhostModeSchedule {
+    /// 各阶段持续时间（秒）
+    phases: &'static [f32; 7],
+    /// 当前阶段下标
+    pub phase: usize,
+    /// 当前阶段已经过的时间
+    pub timer: f32,
+}
+
+impl Default for GhostModeSchedule {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl GhostModeSchedule {
+    pub fn new(level: u32) -> Self {
+        let phases = match level {
+            0 | 1 => &SCHEDULE_1,
+            2..=4 => &SCHEDULE_2_4,
+            _ => &SCHEDULE_5,
+        };
+        Self {
+            phases,
+            phase: 0,
+            timer: 0.0,
+        }
+    }
+
+    /// 从第一个阶段重新开始，失去生命后使用
+    pub fn restart(&mut self) {
+        self.phase = 0;
+        self.timer = 0.0;
+    }
+
+    /// 当前阶段对应的幽灵模式
+    pub fn mode(&self) -> GhostMode {
+        if self.phase.is_multiple_of(2) && self.phase < self.phases.len() {
+            GhostMode::Scatter
+        } else {
+            GhostMode::Chase
+        }
+    }
+
+    /// 推进时间表
+    ///
+    /// ## Params
+    /// - delta_secs: 经过的时间
+    /// - frightened: 是否有幽灵处于恐惧状态，为 true 时时间表暂停
+    ///
+    /// ## Return
+    /// 模式发生切换时返回新模式
+    pub fn tick(&mut self, delta_secs: f32, frightened: bool) -> Option<GhostMode> {
+        if frightened {
+            return None;
+        }
+
+        let old_mode = self.mode();
+        self.timer += delta_secs;
+        while let Some(&duration) = self.phases.get(self.phase) {
+            if self.timer < duration {
+                break;
+            }
+            self.timer -= duration;
+            self.phase += 1;
+        }
+
+        let mode = self.mode();
+        (mode != old_mode).then_some(mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_phases() {
+        let mut schedule = GhostModeSchedule::new(1);
+        assert_eq!(schedule.mode(), GhostMode::Scatter);
+
+        assert_eq!(schedule.tick(6.9, false), None);
+        assert_eq!(schedule.tick(0.2, false), Some(GhostMode::Chase));
+
+        // 恐惧状态时暂停
+        assert_eq!(schedule.tick(100.0, true), None);
+        assert_eq!(schedule.phase, 1);
+
+        assert_eq!(schedule.tick(20.0, false), Some(GhostMode::Scatter));
+
+        // 走完所有阶段后永久追捕
+        schedule.tick(1000.0, false);
+        assert_eq!(schedule.phase, 7);
+        assert_eq!(schedule.mode(), GhostMode::Chase);
+        assert_eq!(schedule.tick(1000.0, false), None);
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{HIGH_SCORE_COUNT, HIGH_SCORE_DIR, INITIALS_LEN};
+
+/// 排行榜中的一条记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    /// 三个字母的玩家缩写
+    pub initials: String,
+    pub score: u32,
+    /// 到达的关卡
+    pub level: u32,
+}
+
+/// 一张地图的排行榜，按分数从高到低保存前 HIGH_SCORE_COUNT 名
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScoreTable {
+    entries: Vec<HighScoreEntry>,
+}
+
+impl HighScoreTable {
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    /// 榜上的最高分，空榜为 0
+    pub fn best(&self) -> u32 {
+        self.entries.first().map_or(0, |entry| entry.score)
+    }
+
+    /// 该分数是否能上榜
+    ///
+    /// 同分时先上榜的排在前面，榜满时同分不能挤掉原有的记录
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < HIGH_SCORE_COUNT
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// 插入一条记录，超出的名次被挤出榜单
+    ///
+    /// ## Return
+    /// 上榜时返回名次（从 0 开始），没有上榜返回 None
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self
+            .entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(HIGH_SCORE_COUNT);
+        Some(rank)
+    }
+
+    /// 指定地图的排行榜文件，保存在档案目录中
+    pub fn path(profile_dir: &Path, map_path: &Path) -> PathBuf {
+        let map_name = map_path
+            .file_stem()
+            .map_or_else(|| "map".into(), |stem| stem.to_string_lossy());
+        profile_dir
+            .join(HIGH_SCORE_DIR)
+            .join(format!("{}.ron", map_name))
+    }
+
+    /// 从文件读取排行榜
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read high scores {}", path.display()))?;
+        ron::from_str(&text)
+            .with_context(|| format!("Failed to parse high scores {}", path.display()))
+    }
+
+    /// 保存到文件，目录不存在时自动创建
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, text)
+            .with_context(|| format!("Failed to write high scores {}", path.display()))
+    }
+}
+
+/// 当前地图的排行榜
+#[derive(Resource, Default)]
+pub struct HighScores {
+    pub table: HighScoreTable,
+}
+
+/// 游戏结束后输入缩写，街机风格：上下键切换字母，左右键移动光标
+#[derive(Resource, Debug, Clone)]
+pub struct InitialsEntry {
+    pub letters: [u8; INITIALS_LEN],
+    /// 正在输入的字母
+    pub cursor: usize,
+    /// 本局的分数和到达的关卡
+    pub score: u32,
+    pub level: u32,
+    /// 确认后在排行榜中的名次
+    pub rank: Option<usize>,
+}
+
+impl InitialsEntry {
+    pub fn new(score: u32, level: u32) -> Self {
+        Self {
+            letters: [b'A'; INITIALS_LEN],
+            cursor: 0,
+            score,
+            level,
+            rank: None,
+        }
+    }
+
+    /// 切换光标处的字母，在 A 和 Z 之间循环
+    pub fn cycle(&mut self, step: i8) {
+        let letter = &mut self.letters[self.cursor];
+        *letter = b'A' + (*letter - b'A' + 26).wrapping_add_signed(step) % 26;
+    }
+
+    /// 移动光标，停在第一个和最后一个字母上
+    pub fn move_cursor(&mut self, step: isize) {
+        self.cursor = self
+            .cursor
+            .saturating_add_signed(step)
+            .min(INITIALS_LEN - 1);
+    }
+
+    /// 直接输入光标处的字母，然后移到下一个字母
+    pub fn type_letter(&mut self, letter: u8) {
+        self.letters[self.cursor] = letter.to_ascii_uppercase();
+        self.move_cursor(1);
+    }
+
+    pub fn initials(&self) -> String {
+        String::from_utf8_lossy(&self.letters).into_owned()
+    }
+
+    /// 生成排行榜记录
+    pub fn entry(&self) -> HighScoreEntry {
+        HighScoreEntry {
+            initials: self.initials(),
+            score: self.score,
+            level: self.level,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(initials: &str, score: u32) -> HighScoreEntry {
+        HighScoreEntry {
+            initials: initials.into(),
+            score,
+            level: 1,
+        }
+    }
+
+    #[test]
+    fn test_high_score_table_insert() {
+        let mut table = HighScoreTable::default();
+        assert!(!table.qualifies(0));
+        assert_eq!(table.insert(entry("AAA", 100)), Some(0));
+        assert_eq!(table.insert(entry("BBB", 300)), Some(0));
+        // 同分排在先上榜的后面
+        assert_eq!(table.insert(entry("CCC", 100)), Some(2));
+        assert_eq!(table.best(), 300);
+
+        for score in 1..=HIGH_SCORE_COUNT as u32 {
+            table.insert(entry("DDD", 1000 + score));
+        }
+        assert_eq!(table.entries().len(), HIGH_SCORE_COUNT);
+        assert!(!table.qualifies(1001));
+        assert_eq!(table.insert(entry("EEE", 1001)), None);
+        assert_eq!(table.insert(entry("FFF", 1002)), Some(HIGH_SCORE_COUNT - 1));
+        assert_eq!(table.entries().last().unwrap().initials, "FFF");
+    }
+
+    #[test]
+    fn test_initials_entry() {
+        let mut initials = InitialsEntry::new(500, 2);
+        initials.cycle(-1);
+        initials.move_cursor(1);
+        initials.cycle(2);
+        initials.move_cursor(5);
+        initials.type_letter(b'x');
+        initials.move_cursor(-5);
+        assert_eq!(initials.cursor, 0);
+        assert_eq!(initials.initials(), "ZCX");
+    }
+}
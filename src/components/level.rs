@@ -0,0 +1,48 @@
+use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::LevelSpec;
+
+/// 当前关卡
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Level {
+    /// 关卡编号，从 1 开始
+    pub value: u32,
+    /// 进入本关时的分数，重玩本关时恢复
+    pub start_score: u32,
+    /// 本关已吃掉的豆子数
+    pub pellets_eaten: u32,
+    /// 本关已出现过的水果数
+    pub fruits_spawned: usize,
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self {
+            value: 1,
+            start_score: 0,
+            pellets_eaten: 0,
+            fruits_spawned: 0,
+        }
+    }
+}
+
+impl Level {
+    /// 本关的速度和计时参数
+    pub fn spec(&self) -> &'static LevelSpec {
+        LevelSpec::for_level(self.value)
+    }
+
+    /// 进入下一关
+    pub fn advance(&mut self, score: u32) {
+        self.value += 1;
+        self.start_score = score;
+        self.reset_progress();
+    }
+
+    /// 清空本关的进度计数
+    pub fn reset_progress(&mut self) {
+        self.pellets_eaten = 0;
+        self.fruits_spawned = 0;
+    }
+}
@@ -0,0 +1,161 @@
+use crate::MAX_SPEED;
+
+/// 某一关的速度比例，均为相对 MAX_SPEED 的百分比
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedTable {
+    /// 玩家正常速度
+    pub player: f32,
+    /// 玩家吃豆时的速度
+    pub player_eating: f32,
+    /// 幽灵恐惧时玩家的速度
+    pub player_frightened: f32,
+    /// 幽灵恐惧时玩家吃豆的速度
+    pub player_frightened_eating: f32,
+    /// 幽灵正常速度
+    pub ghost: f32,
+    /// 幽灵恐惧时的速度
+    pub ghost_frightened: f32,
+    /// 幽灵在隧道中的速度
+    pub ghost_tunnel: f32,
+}
+
+/// 第 1 关
+const SPEEDS_1: SpeedTable = SpeedTable {
+    player: 0.80,
+    player_eating: 0.71,
+    player_frightened: 0.90,
+    player_frightened_eating: 0.79,
+    ghost: 0.75,
+    ghost_frightened: 0.50,
+    ghost_tunnel: 0.40,
+};
+
+/// 第 2 ~ 4 关
+const SPEEDS_2_4: SpeedTable = SpeedTable {
+    player: 0.90,
+    player_eating: 0.79,
+    player_frightened: 0.95,
+    player_frightened_eating: 0.83,
+    ghost: 0.85,
+    ghost_frightened: 0.55,
+    ghost_tunnel: 0.45,
+};
+
+/// 第 5 ~ 20 关
+const SPEEDS_5_20: SpeedTable = SpeedTable {
+    player: 1.00,
+    player_eating: 0.87,
+    player_frightened: 1.00,
+    player_frightened_eating: 0.87,
+    ghost: 0.95,
+    ghost_frightened: 0.60,
+    ghost_tunnel: 0.50,
+};
+
+/// 第 21 关及之后
+const SPEEDS_21: SpeedTable = SpeedTable {
+    player: 0.90,
+    player_eating: 0.79,
+    player_frightened: 0.90,
+    player_frightened_eating: 0.79,
+    ghost: 0.95,
+    ghost_frightened: 0.60,
+    ghost_tunnel: 0.50,
+};
+
+/// 关卡参数：速度、恐惧持续时间和闪烁次数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelSpec {
+    pub speeds: SpeedTable,
+    /// 恐惧状态持续时间（秒），为 0 时幽灵不再变蓝
+    pub frightened_time: f32,
+    /// 恐惧状态结束前的闪烁次数
+    pub frightened_flashes: u32,
+}
+
+impl LevelSpec {
+    const fn new(speeds: SpeedTable, frightened_time: f32, frightened_flashes: u32) -> Self {
+        Self {
+            speeds,
+            frightened_time,
+            frightened_flashes,
+        }
+    }
+
+    /// 获取指定关卡的参数，超出表格的关卡使用最后一行
+    pub fn for_level(level: u32) -> &'static Self {
+        let index = (level.max(1) as usize - 1).min(LEVEL_SPECS.len() - 1);
+        &LEVEL_SPECS[index]
+    }
+
+    /// 玩家速度（格/秒）
+    ///
+    /// ## Params
+    /// - frightened: 是否有幽灵处于恐惧状态
+    /// - eating: 是否正在吃豆
+    pub fn player_speed(&self, frightened: bool, eating: bool) -> f32 {
+        let ratio = match (frightened, eating) {
+            (false, false) => self.speeds.player,
+            (false, true) => self.speeds.player_eating,
+            (true, false) => self.speeds.player_frightened,
+            (true, true) => self.speeds.player_frightened_eating,
+        };
+        ratio * MAX_SPEED
+    }
+
+    /// 幽灵正常速度（格/秒）
+    pub fn ghost_speed(&self) -> f32 {
+        self.speeds.ghost * MAX_SPEED
+    }
+
+    /// 幽灵恐惧时的速度（格/秒）
+    pub fn ghost_frightened_speed(&self) -> f32 {
+        self.speeds.ghost_frightened * MAX_SPEED
+    }
+
+    /// 幽灵在隧道中的速度（格/秒）
+    pub fn ghost_tunnel_speed(&self) -> f32 {
+        self.speeds.ghost_tunnel * MAX_SPEED
+    }
+}
+
+/// 原版吃豆人的关卡参数表，下标为关卡编号 - 1
+pub const LEVEL_SPECS: [LevelSpec; 21] = [
+    LevelSpec::new(SPEEDS_1, 6.0, 5),
+    LevelSpec::new(SPEEDS_2_4, 5.0, 5),
+    LevelSpec::new(SPEEDS_2_4, 4.0, 5),
+    LevelSpec::new(SPEEDS_2_4, 3.0, 5),
+    LevelSpec::new(SPEEDS_5_20, 2.0, 5),
+    LevelSpec::new(SPEEDS_5_20, 5.0, 5),
+    LevelSpec::new(SPEEDS_5_20, 2.0, 5),
+    LevelSpec::new(SPEEDS_5_20, 2.0, 5),
+    LevelSpec::new(SPEEDS_5_20, 1.0, 3),
+    LevelSpec::new(SPEEDS_5_20, 5.0, 5),
+    LevelSpec::new(SPEEDS_5_20, 2.0, 5),
+    LevelSpec::new(SPEEDS_5_20, 1.0, 3),
+    LevelSpec::new(SPEEDS_5_20, 1.0, 3),
+    LevelSpec::new(SPEEDS_5_20, 3.0, 5),
+    LevelSpec::new(SPEEDS_5_20, 1.0, 3),
+    LevelSpec::new(SPEEDS_5_20, 1.0, 3),
+    LevelSpec::new(SPEEDS_5_20, 0.0, 0),
+    LevelSpec::new(SPEEDS_5_20, 1.0, 3),
+    LevelSpec::new(SPEEDS_5_20, 0.0, 0),
+    LevelSpec::new(SPEEDS_5_20, 0.0, 0),
+    LevelSpec::new(SPEEDS_21, 0.0, 0),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_spec_lookup() {
+        assert_eq!(LevelSpec::for_level(0), &LEVEL_SPECS[0]);
+        assert_eq!(LevelSpec::for_level(1).frightened_time, 6.0);
+        assert_eq!(LevelSpec::for_level(17).frightened_flashes, 0);
+        assert_eq!(LevelSpec::for_level(999), &LEVEL_SPECS[20]);
+
+        // 后面的关卡幽灵更快
+        assert!(LevelSpec::for_level(5).ghost_speed() > LevelSpec::for_level(1).ghost_speed());
+    }
+}
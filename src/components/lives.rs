@@ -0,0 +1,115 @@
+use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{EXTRA_LIFE_SCORE, INITIAL_LIVES};
+
+/// 剩余生命数
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lives {
+    pub value: u32,
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self {
+            value: INITIAL_LIVES,
+        }
+    }
+}
+
+impl Lives {
+    /// 失去一条命
+    ///
+    /// ## Return
+    /// 若还有剩余生命则返回 true
+    pub fn lose_one(&mut self) -> bool {
+        self.value = self.value.saturating_sub(1);
+        self.value > 0
+    }
+}
+
+/// 奖励生命规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtraLifeRule {
+    /// 不奖励
+    Disabled,
+    /// 分数首次达到阈值时奖励一次
+    Once(u32),
+    /// 分数每增加 N 奖励一次
+    Every(u32),
+}
+
+impl Default for ExtraLifeRule {
+    fn default() -> Self {
+        Self::Once(EXTRA_LIFE_SCORE)
+    }
+}
+
+/// 奖励生命追踪，记录本局已经按规则奖励过的生命数
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtraLifeTracker {
+    pub rule: ExtraLifeRule,
+    /// 本局已奖励的生命数
+    pub awarded: u32,
+}
+
+impl ExtraLifeTracker {
+    pub fn new(rule: ExtraLifeRule) -> Self {
+        Self { rule, awarded: 0 }
+    }
+
+    /// 根据当前分数检查是否应奖励生命
+    ///
+    /// ## Return
+    /// 本次新奖励的生命数
+    pub fn check(&mut self, score: u32) -> u32 {
+        let earned = match self.rule {
+            ExtraLifeRule::Disabled => 0,
+            ExtraLifeRule::Once(threshold) => u32::from(score >= threshold),
+            ExtraLifeRule::Every(0) => 0,
+            ExtraLifeRule::Every(step) => score / step,
+        };
+
+        let new_lives = earned.saturating_sub(self.awarded);
+        self.awarded += new_lives;
+        new_lives
+    }
+
+    /// 新的一局重新开始计算，规则保持不变
+    pub fn reset(&mut self) {
+        self.awarded = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_life_once() {
+        let mut tracker = ExtraLifeTracker::new(ExtraLifeRule::Once(10_000));
+        assert_eq!(tracker.check(9_999), 0);
+        assert_eq!(tracker.check(10_000), 1);
+        assert_eq!(tracker.check(50_000), 0);
+
+        tracker.reset();
+        assert_eq!(tracker.check(12_000), 1);
+    }
+
+    #[test]
+    fn test_extra_life_every() {
+        let mut tracker = ExtraLifeTracker::new(ExtraLifeRule::Every(5_000));
+        assert_eq!(tracker.check(4_999), 0);
+        assert_eq!(tracker.check(5_000), 1);
+        // 一次跨过多个阈值
+        assert_eq!(tracker.check(16_000), 2);
+        assert_eq!(tracker.check(16_500), 0);
+    }
+
+    #[test]
+    fn test_extra_life_disabled() {
+        let mut tracker = ExtraLifeTracker::new(ExtraLifeRule::Disabled);
+        assert_eq!(tracker.check(1_000_000), 0);
+        assert_eq!(ExtraLifeTracker::new(ExtraLifeRule::Every(0)).check(100), 0);
+    }
+}
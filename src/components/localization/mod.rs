@@ -1,21 +1,17 @@
 pub mod texts;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 pub use texts::*;
 
 /// 支持的语言枚举
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum Language {
+    #[default]
     English,
     // Chinese,
 }
 
-impl Default for Language {
-    fn default() -> Self {
-        Self::English
-    }
-}
-
 /// 语言设置资源
 #[derive(Resource, Debug, Clone)]
 pub struct LanguageSettings {
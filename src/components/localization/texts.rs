@@ -5,7 +5,62 @@ pub struct LocalizedTexts {
     // UI 文本
     pub game_title: &'static str,
     pub start_button: &'static str,
+    pub replay_button: &'static str,
+    pub continue_button: &'static str,
+    pub overwrite_slot_button: &'static str,
+    pub high_scores_button: &'static str,
+    pub statistics_button: &'static str,
     pub quit_button: &'static str,
+    // 暂停菜单
+    pub paused_title: &'static str,
+    pub resume_button: &'static str,
+    pub restart_button: &'static str,
+    pub menu_button: &'static str,
+    // 游戏结束
+    pub game_over_title: &'static str,
+    pub score_label: &'static str,
+    pub level_label: &'static str,
+    pub best_score_label: &'static str,
+    pub retry_button: &'static str,
+    // 排行榜
+    pub high_scores_title: &'static str,
+    pub no_high_scores: &'static str,
+    pub back_button: &'static str,
+    pub enter_initials: &'static str,
+    pub initials_hint: &'static str,
+    pub new_high_score: &'static str,
+    // 累计统计
+    pub statistics_title: &'static str,
+    pub power_pellets_label: &'static str,
+    pub ghosts_eaten_label: &'static str,
+    pub deaths_label: &'static str,
+    pub levels_cleared_label: &'static str,
+    pub fastest_level_label: &'static str,
+    pub play_time_label: &'static str,
+    // 成就
+    pub achievement_unlocked: &'static str,
+    // 玩家档案
+    pub profiles_title: &'static str,
+    pub no_profiles: &'static str,
+    pub new_profile_button: &'static str,
+    pub rename_button: &'static str,
+    pub delete_button: &'static str,
+    pub confirm_delete_button: &'static str,
+    pub profile_name_label: &'static str,
+    pub profile_name_hint: &'static str,
+    pub profile_label: &'static str,
+    pub difficulty_label: &'static str,
+    pub easy_difficulty: &'static str,
+    pub normal_difficulty: &'static str,
+    pub hard_difficulty: &'static str,
+    pub keys_label: &'static str,
+    pub custom_keys: &'static str,
+    pub mode_label: &'static str,
+    pub casual_mode: &'static str,
+    pub ranked_mode: &'static str,
+    // 个人最佳回放
+    pub ghost_run_label: &'static str,
+    pub pellets_label: &'static str,
 }
 
 /// 英文文本
@@ -13,5 +68,60 @@ pub const ENGLISH_TEXTS: LocalizedTexts = LocalizedTexts {
     // UI 文本
     game_title: "Pac-Man",
     start_button: "Start",
+    replay_button: "Watch Replay",
+    continue_button: "Continue",
+    overwrite_slot_button: "Overwrite Slot",
+    high_scores_button: "High Scores",
+    statistics_button: "Statistics",
     quit_button: "Quit",
+    // 暂停菜单
+    paused_title: "Paused",
+    resume_button: "Resume",
+    restart_button: "Restart Level",
+    menu_button: "Main Menu",
+    // 游戏结束
+    game_over_title: "Game Over",
+    score_label: "Score",
+    level_label: "Level",
+    best_score_label: "Best",
+    retry_button: "Retry",
+    // 排行榜
+    high_scores_title: "High Scores",
+    no_high_scores: "No scores yet",
+    back_button: "Back",
+    enter_initials: "Enter Initials",
+    initials_hint: "Up/Down or type a letter, Enter to confirm",
+    new_high_score: "New High Score",
+    // 累计统计
+    statistics_title: "Statistics",
+    power_pellets_label: "Power Pellets",
+    ghosts_eaten_label: "Eaten",
+    deaths_label: "Caught You",
+    levels_cleared_label: "Levels Cleared",
+    fastest_level_label: "Fastest Level",
+    play_time_label: "Play Time",
+    // 成就
+    achievement_unlocked: "Achievement Unlocked",
+    // 玩家档案
+    profiles_title: "Select Profile",
+    no_profiles: "Create a profile to start",
+    new_profile_button: "New Profile",
+    rename_button: "Rename",
+    delete_button: "Delete",
+    confirm_delete_button: "Confirm?",
+    profile_name_label: "Profile Name",
+    profile_name_hint: "Type a name, Enter to confirm, Esc to cancel",
+    profile_label: "Profile",
+    difficulty_label: "Difficulty",
+    easy_difficulty: "Easy",
+    normal_difficulty: "Normal",
+    hard_difficulty: "Hard",
+    keys_label: "Keys",
+    custom_keys: "Custom",
+    mode_label: "Mode",
+    casual_mode: "Casual",
+    ranked_mode: "Ranked",
+    // 个人最佳回放
+    ghost_run_label: "vs Best",
+    pellets_label: "Pellets",
 };
@@ -1,6 +1,8 @@
 use std::{fs, path::Path};
 
 use bevy::ecs::resource::Resource;
+use glam::{IVec2, Vec2};
+use serde::{Deserialize, Serialize};
 
 /// 单元格类型
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -13,16 +15,58 @@ pub enum TileType {
     PinkyGhost,
     InkyGhost,
     ClydeGhost,
+    /// 幽灵基地的门，只有幽灵可以通过
+    HouseDoor,
 }
 
+impl TileType {
+    /// 文本地图中的字符
+    pub fn from_char(ch: char) -> Self {
+        match ch {
+            '#' => Self::Wall,
+            '.' => Self::Pellet,
+            'P' => Self::Player,
+            '2' => Self::BlinkyGhost,
+            '3' => Self::PinkyGhost,
+            '4' => Self::InkyGhost,
+            '5' => Self::ClydeGhost,
+            '-' => Self::HouseDoor,
+            _ => Self::Empty,
+        }
+    }
+
+    /// 转换为文本地图中的字符
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Empty => ' ',
+            Self::Wall => '#',
+            Self::Pellet => '.',
+            Self::Player => 'P',
+            Self::BlinkyGhost => '2',
+            Self::PinkyGhost => '3',
+            Self::InkyGhost => '4',
+            Self::ClydeGhost => '5',
+            Self::HouseDoor => '-',
+        }
+    }
+}
+
+/// 文本地图中标记奖励水果出现位置的字符，这一格同时也有豆子
+pub const FRUIT_SPAWN_CHAR: char = 'F';
+
 /// 地图数据
 /// 注意：只有数组访问是 ```tiles[y][x]```，其他一律为 (x, y)
-#[derive(Debug, Resource)]
+///
+/// 序列化时按文本地图的格式保存为每行一个字符串
+#[derive(Debug, Clone, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(into = "Vec<String>", from = "Vec<String>")]
 pub struct MapData {
     pub width: usize,
     pub height: usize,
     /// 逻辑坐标即二维数组下标，采用行优先（y），向右为x轴正方向，向下为y轴正方向
     pub tiles: Vec<Vec<TileType>>,
+    /// 奖励水果出现的位置，地图中没有标记时不出现水果
+    pub fruit_spawn: Option<IVec2>,
 }
 
 impl MapData {
@@ -31,6 +75,7 @@ impl MapData {
             width,
             height,
             tiles: vec![vec![TileType::Empty; width]; height],
+            fruit_spawn: None,
         }
     }
 
@@ -42,6 +87,10 @@ impl MapData {
         self.tiles[y][x] == TileType::Pellet
     }
 
+    pub fn is_house_door(&self, x: usize, y: usize) -> bool {
+        self.tiles[y][x] == TileType::HouseDoor
+    }
+
     pub fn is_player(&self, x: usize, y: usize) -> bool {
         self.tiles[y][x] == TileType::Player
     }
@@ -54,9 +103,119 @@ impl MapData {
         self.tiles[y][x]
     }
 
+    /// 查找第一个指定类型的单元格
+    pub fn find_tile(&self, tile_type: TileType) -> Option<IVec2> {
+        self.tiles.iter().enumerate().find_map(|(y, row)| {
+            row.iter()
+                .position(|&tile| tile == tile_type)
+                .map(|x| IVec2::new(x as i32, y as i32))
+        })
+    }
+
+    /// 基地出口：基地门外侧紧挨着门的格子
+    ///
+    /// ## Params
+    /// - inside: 基地内的某个位置，用于判断门的哪一侧是外侧
+    pub fn house_exit(&self, inside: IVec2) -> Option<IVec2> {
+        let door = self.find_tile(TileType::HouseDoor)?;
+        let outward = IVec2::new(0, (door.y - inside.y).signum());
+        self.neighbor(door, outward)
+    }
+
+    /// 地图中剩余的豆子数量
+    pub fn remaining_pellets(&self) -> usize {
+        self.tiles
+            .iter()
+            .flatten()
+            .filter(|&&tile| tile == TileType::Pellet)
+            .count()
+    }
+
     pub fn is_valid_position(&self, x: i32, y: i32) -> bool {
         x >= 0 && (x as usize) < self.width && y >= 0 && (y as usize) < self.height
     }
+
+    /// 第 y 行左右两端是否都开放（水平隧道）
+    pub fn is_horizontal_tunnel(&self, y: usize) -> bool {
+        self.width > 0 && !self.is_wall(0, y) && !self.is_wall(self.width - 1, y)
+    }
+
+    /// 第 x 列上下两端是否都开放（垂直隧道）
+    pub fn is_vertical_tunnel(&self, x: usize) -> bool {
+        self.height > 0 && !self.is_wall(x, 0) && !self.is_wall(x, self.height - 1)
+    }
+
+    /// 将越界坐标经隧道折返到地图另一侧
+    ///
+    /// ## Return
+    /// 坐标在地图内时原样返回；越界且所在行/列不是隧道时返回 None
+    pub fn wrap_position(&self, pos: IVec2) -> Option<IVec2> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let mut wrapped = pos;
+
+        if pos.x < 0 || pos.x >= width {
+            if pos.y < 0 || pos.y >= height || !self.is_horizontal_tunnel(pos.y as usize) {
+                return None;
+            }
+            wrapped.x = pos.x.rem_euclid(width);
+        }
+
+        if pos.y < 0 || pos.y >= height {
+            if !self.is_vertical_tunnel(wrapped.x as usize) {
+                return None;
+            }
+            wrapped.y = pos.y.rem_euclid(height);
+        }
+
+        Some(wrapped)
+    }
+
+    /// 是否位于边缘隧道中：所在行（列）两端开放，且从该格到地图边缘都是两侧为墙的通道
+    pub fn is_tunnel(&self, pos: IVec2) -> bool {
+        if !self.is_valid_position(pos.x, pos.y) {
+            return false;
+        }
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        let is_wall_or_edge =
+            |x: i32, y: i32| !self.is_valid_position(x, y) || self.is_wall(x as usize, y as usize);
+        let horizontal_corridor = |x: usize| {
+            is_wall_or_edge(x as i32, y as i32 - 1) && is_wall_or_edge(x as i32, y as i32 + 1)
+        };
+        let vertical_corridor = |y: usize| {
+            is_wall_or_edge(x as i32 - 1, y as i32) && is_wall_or_edge(x as i32 + 1, y as i32)
+        };
+
+        (self.is_horizontal_tunnel(y)
+            && ((0..=x).all(horizontal_corridor) || (x..self.width).all(horizontal_corridor)))
+            || (self.is_vertical_tunnel(x)
+                && ((0..=y).all(vertical_corridor) || (y..self.height).all(vertical_corridor)))
+    }
+
+    /// 从 pos 沿 dir 走一格后的位置，支持穿过地图边缘的隧道
+    ///
+    /// ## Return
+    /// 若目标格是墙或无法到达则返回 None
+    pub fn neighbor(&self, pos: IVec2, dir: IVec2) -> Option<IVec2> {
+        let next = self.wrap_position(pos + dir)?;
+        (!self.is_wall(next.x as usize, next.y as usize)).then_some(next)
+    }
+
+    /// 考虑隧道折返的曼哈顿距离下界
+    pub fn wrapped_distance(&self, a: IVec2, b: IVec2) -> i32 {
+        let dx = (a.x - b.x).abs();
+        let dy = (a.y - b.y).abs();
+        dx.min(self.width as i32 - dx) + dy.min(self.height as i32 - dy)
+    }
+
+    /// 两个连续坐标之间考虑隧道折返的欧氏距离
+    ///
+    /// 隧道两端的角色在坐标上相差接近地图宽度，实际只隔着很短的距离
+    pub fn wrapped_euclidean_distance(&self, a: Vec2, b: Vec2) -> f32 {
+        let (width, height) = (self.width as f32, self.height as f32);
+        let dx = (a.x - b.x).abs();
+        let dy = (a.y - b.y).abs();
+        Vec2::new(dx.min(width - dx), dy.min(height - dy)).length()
+    }
 }
 
 /// 地图加载器
@@ -73,25 +232,52 @@ impl MapLoader for TextMapLoader {
             .lines()
             .map(|line| line.to_string())
             .collect::<Vec<_>>();
+        Ok(MapData::from(lines))
+    }
+}
+
+/// 从文本地图的每一行生成地图，宽度以第一行为准
+impl From<Vec<String>> for MapData {
+    fn from(lines: Vec<String>) -> Self {
         let height = lines.len();
-        let width = lines.get(0).map_or(0, |l| l.len());
+        let width = lines.first().map_or(0, |l| l.len());
         let mut map = MapData::new(width, height);
 
         for (y, line) in lines.iter().enumerate() {
-            for (x, ch) in line.chars().enumerate() {
-                map.tiles[y][x] = match ch {
-                    '#' => TileType::Wall,
-                    '.' => TileType::Pellet,
-                    'P' => TileType::Player,
-                    '2' => TileType::BlinkyGhost,
-                    '3' => TileType::PinkyGhost,
-                    '4' => TileType::InkyGhost,
-                    '5' => TileType::ClydeGhost,
-                    _ => TileType::Empty,
+            for (x, ch) in line.chars().take(width).enumerate() {
+                if ch == FRUIT_SPAWN_CHAR {
+                    map.tiles[y][x] = TileType::Pellet;
+                    map.fruit_spawn = Some(IVec2::new(x as i32, y as i32));
+                } else {
+                    map.tiles[y][x] = TileType::from_char(ch);
                 }
             }
         }
-        Ok(map)
+        map
+    }
+}
+
+/// 水果位置上的豆子还在时写回水果标记
+impl From<MapData> for Vec<String> {
+    fn from(map: MapData) -> Self {
+        map.tiles
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, &tile)| {
+                        let is_fruit_spawn =
+                            map.fruit_spawn == Some(IVec2::new(x as i32, y as i32));
+                        if is_fruit_spawn && tile == TileType::Pellet {
+                            FRUIT_SPAWN_CHAR
+                        } else {
+                            tile.to_char()
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
     }
 }
 
@@ -103,16 +289,56 @@ pub fn check_position(x: i32, y: i32, width: usize, height: usize) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const PATH: &'static str = "assets\\map\\pacman.map";
+    use crate::MAP_PATH;
 
     #[test]
     fn test_text_map_loader() -> anyhow::Result<()> {
         let map_loader = TextMapLoader;
-        let map_data = map_loader.load_map(Path::new(PATH))?;
+        let map_data = map_loader.load_map(Path::new(MAP_PATH))?;
 
         assert_eq!(map_data.height, 31);
         assert_eq!(map_data.width, 28);
+        assert!(map_data.remaining_pellets() > 0);
+        // 水果出现的位置同时保留豆子
+        assert_eq!(map_data.fruit_spawn, Some(IVec2::new(13, 17)));
+        assert!(map_data.is_pellet(13, 17));
+        assert_eq!(
+            MapData::from(Vec::<String>::from(map_data.clone())),
+            map_data
+        );
+        assert_eq!(
+            map_data.house_exit(IVec2::new(12, 14)),
+            Some(IVec2::new(13, 11))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tunnel_wrap() -> anyhow::Result<()> {
+        let map_data = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+
+        // 第14行左右两端开放
+        assert!(map_data.is_horizontal_tunnel(14));
+        assert_eq!(
+            map_data.neighbor(IVec2::new(0, 14), IVec2::new(-1, 0)),
+            Some(IVec2::new(27, 14))
+        );
+        assert_eq!(
+            map_data.neighbor(IVec2::new(27, 14), IVec2::new(1, 0)),
+            Some(IVec2::new(0, 14))
+        );
+
+        // 其余边缘仍然是墙
+        assert!(!map_data.is_horizontal_tunnel(1));
+        assert_eq!(map_data.wrap_position(IVec2::new(-1, 1)), None);
+        assert_eq!(map_data.wrap_position(IVec2::new(1, -1)), None);
+
+        // 隧道只包括两侧为墙的通道部分
+        assert!(map_data.is_tunnel(IVec2::new(0, 14)));
+        assert!(map_data.is_tunnel(IVec2::new(5, 14)));
+        assert!(!map_data.is_tunnel(IVec2::new(6, 14)));
+        assert!(map_data.is_tunnel(IVec2::new(27, 14)));
+        assert!(!map_data.is_tunnel(IVec2::new(1, 1)));
         Ok(())
     }
 }
@@ -1,19 +1,43 @@
+mod achievement;
+mod data_dir;
 mod events;
+mod fruit;
+mod game_rng;
+mod high_score;
+mod level;
+mod level_spec;
+mod lives;
 pub(crate) mod localization;
 mod map;
 mod player;
+mod profile;
 mod score;
 mod state;
+mod stats;
 mod pellet;
 mod movement;
 mod ghost;
+mod ghost_house;
+mod ghost_mode_schedule;
 
+pub use achievement::*;
+pub use data_dir::*;
 pub use events::*;
-pub use localization::LanguageSettings;
+pub use fruit::*;
+pub use game_rng::*;
+pub use high_score::*;
+pub use level::*;
+pub use level_spec::*;
+pub use lives::*;
+pub use localization::{ChangeLanguageEvent, LanguageSettings};
 pub use map::*;
 pub use player::*;
+pub use profile::*;
 pub use score::*;
 pub use state::GameState;
+pub use stats::*;
 pub use pellet::*;
 pub use movement::*;
 pub use ghost::*;
+pub use ghost_house::*;
+pub use ghost_mode_schedule::*;
@@ -1,4 +1,5 @@
-use glam::IVec2;
+use glam::{IVec2, Vec2};
+use serde::{Deserialize, Serialize};
 
 use crate::MapData;
 
@@ -14,7 +15,7 @@ pub trait TryMove {
 }
 
 /// 移动组件
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Movement {
     /// 方向
     pub direction: IVec2,
@@ -41,6 +42,12 @@ impl Movement {
         self.is_moving = true;
     }
 
+    /// 修改速度，保持当前这一步已完成的比例不变
+    pub fn set_speed(&mut self, speed: f32) {
+        self.accumulated_time *= self.speed / speed;
+        self.speed = speed;
+    }
+
     pub fn start_moving(&mut self) {
         self.is_moving = true;
         self.accumulated_time = 0.0;
@@ -79,8 +86,74 @@ impl Movement {
         }
     }
 
+    /// 当前这一步已完成的比例，范围 [0, 1]
+    ///
+    /// ## Params
+    /// - overstep_secs: 距离上一个模拟帧已经过的时间，渲染插值时使用，模拟中为 0
+    pub fn progress(&self, overstep_secs: f32) -> f32 {
+        if !self.is_moving {
+            return 0.0;
+        }
+        ((self.accumulated_time + overstep_secs) / self.get_move_interval()).clamp(0.0, 1.0)
+    }
+
+    /// 连续坐标（单位：格），用于渲染和碰撞检测
+    ///
+    /// ## Params
+    /// - tile_pos: 当前地图格坐标
+    /// - advancing: 是否正在前往下一格，前方是墙时为 false
+    /// - overstep_secs: 距离上一个模拟帧已经过的时间，渲染插值时使用，模拟中为 0
+    pub fn position(&self, tile_pos: IVec2, advancing: bool, overstep_secs: f32) -> Vec2 {
+        if advancing {
+            tile_pos.as_vec2() + self.direction.as_vec2() * self.progress(overstep_secs)
+        } else {
+            tile_pos.as_vec2()
+        }
+    }
+
+    /// 走到一半时掉头：改为从正在前往的格子往回走，保持连续坐标不变
+    ///
+    /// 调用方需要把当前格子更新为正在前往的格子
+    pub fn reverse(&mut self) {
+        self.direction = -self.direction;
+        self.accumulated_time = (self.get_move_interval() - self.accumulated_time).max(0.0);
+    }
+
     /// 重置累积时间
     pub fn reset_accumulated_time(&mut self) {
         self.accumulated_time = 0.0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continuous_position() {
+        let mut movement = Movement::new(4.0, true);
+        movement.set_direction(IVec2::X);
+        assert!(!movement.update(0.125));
+        assert_eq!(
+            movement.position(IVec2::new(3, 5), true, 0.0),
+            Vec2::new(3.5, 5.0)
+        );
+        assert_eq!(
+            movement.position(IVec2::new(3, 5), false, 0.0),
+            Vec2::new(3.0, 5.0)
+        );
+
+        // 改变速度不影响已完成的比例
+        movement.set_speed(8.0);
+        assert_eq!(movement.progress(0.0), 0.5);
+
+        // 掉头后从下一格往回走，位置不变
+        movement.set_speed(4.0);
+        movement.accumulated_time = 0.0625;
+        movement.reverse();
+        assert_eq!(
+            movement.position(IVec2::new(4, 5), true, 0.0),
+            Vec2::new(3.25, 5.0)
+        );
+    }
+}
@@ -1,23 +1,130 @@
-use bevy::ecs::component::Component;
-use glam::IVec2;
+use bevy::ecs::{component::Component, resource::Resource};
+use glam::{IVec2, Vec2};
+use serde::{Deserialize, Serialize};
 
-use crate::{MapData, Movement, TryMove};
+use crate::{LevelSpec, MapData, Movement, PLAYER_PRE_TURN_WINDOW, SIMULATION_HZ, TryMove};
+
+/// 渲染帧中采集、等待下一个模拟帧处理的玩家输入
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct PlayerInput {
+    /// 最近按下的方向
+    pub direction: Option<IVec2>,
+}
 
 /// 玩家
-#[derive(Debug, Component)]
+#[derive(Debug, Clone, Component, Serialize, Deserialize)]
 pub struct Player {
     /// 当前地图格坐标
     pub tile_pos: IVec2,
+    /// 本帧移动前的地图格坐标
+    pub prev_tile_pos: IVec2,
+    /// 初始出生位置
+    pub spawn_pos: IVec2,
     /// 移动组件
     pub movement: Movement,
+    /// 缓冲的期望方向，在能转向之前一直保留
+    pub intended_direction: IVec2,
+    /// 离格子中心多近时可以拐弯（格），为 0 时关闭提前转向
+    pub pre_turn_window: f32,
 }
 
 impl Player {
     pub fn new(x: i32, y: i32) -> Self {
         Self {
             tile_pos: IVec2::new(x, y),
-            movement: Movement::new(6.0, false),
+            prev_tile_pos: IVec2::new(x, y),
+            spawn_pos: IVec2::new(x, y),
+            movement: Movement::new(LevelSpec::for_level(1).player_speed(false, false), false),
+            intended_direction: IVec2::ZERO,
+            pre_turn_window: PLAYER_PRE_TURN_WINDOW,
+        }
+    }
+
+    /// 尝试转向缓冲的期望方向
+    ///
+    /// 拐弯只在离开格子中心不超过 pre_turn_window 时进行，已经走出的距离计入新方向，
+    /// 走得更远时保留期望方向，到下一格再转
+    ///
+    /// ## Params
+    /// - map_data: 地图数据
+    ///
+    /// ## Return
+    /// 是否转向成功
+    pub fn try_turn(&mut self, map_data: &MapData) -> bool {
+        let dir = self.intended_direction;
+        if dir == IVec2::ZERO || (dir == self.movement.direction && self.movement.is_moving) {
+            return false;
+        }
+        if self.next_tile(map_data, dir).is_none() {
+            return false;
+        }
+
+        // 走到一半掉头时从正在前往的格子往回走，画面不会跳动
+        if self.movement.is_moving
+            && dir == -self.movement.direction
+            && self.movement.accumulated_time > 0.0
+            && let Some(next) = self.next_tile(map_data, self.movement.direction)
+        {
+            self.tile_pos = next;
+            self.movement.reverse();
+            return true;
+        }
+
+        let cornering = self.movement.is_moving && dir.dot(self.movement.direction) == 0;
+        if cornering && self.movement.progress(0.0) > self.turn_tolerance() {
+            return false;
         }
+        self.movement.set_direction(dir);
+        true
+    }
+
+    /// 快到拐角时是否可以提前转向
+    ///
+    /// 离下一格的中心不超过 pre_turn_window、且到下一格后缓冲的方向可以走时，
+    /// 提前到达下一格并拐弯，所以玩家过弯比幽灵快
+    pub fn can_pre_turn(&self, map_data: &MapData) -> bool {
+        let dir = self.intended_direction;
+        if !self.movement.is_moving
+            || dir == IVec2::ZERO
+            || dir.dot(self.movement.direction) != 0
+            || 1.0 - self.movement.progress(0.0) > self.pre_turn_window
+        {
+            return false;
+        }
+        self.next_tile(map_data, self.movement.direction)
+            .and_then(|next| map_data.neighbor(next, dir))
+            .is_some_and(|pos| !map_data.is_house_door(pos.x as usize, pos.y as usize))
+    }
+
+    /// 拐弯时允许离开格子中心的距离
+    ///
+    /// 刚到达新格子时已经多走出不超过一个模拟帧的距离，这时拐弯不算提前转向
+    fn turn_tolerance(&self) -> f32 {
+        self.pre_turn_window
+            .max(self.movement.speed / SIMULATION_HZ as f32)
+    }
+
+    /// 从当前位置沿 dir 走一格后的位置，支持隧道，玩家不能进入幽灵基地
+    pub fn next_tile(&self, map_data: &MapData, dir: IVec2) -> Option<IVec2> {
+        map_data
+            .neighbor(self.tile_pos, dir)
+            .filter(|pos| !map_data.is_house_door(pos.x as usize, pos.y as usize))
+    }
+
+    /// 连续坐标（单位：格）
+    ///
+    /// ## Params
+    /// - map_data: 地图数据
+    /// - overstep_secs: 距离上一个模拟帧已经过的时间，渲染插值时使用，模拟中为 0
+    pub fn position(&self, map_data: &MapData, overstep_secs: f32) -> Vec2 {
+        let advancing = self.next_tile(map_data, self.movement.direction).is_some();
+        self.movement
+            .position(self.tile_pos, advancing, overstep_secs)
+    }
+
+    /// 回到出生位置并停止移动
+    pub fn reset(&mut self) {
+        *self = Self::new(self.spawn_pos.x, self.spawn_pos.y);
     }
 }
 
@@ -27,14 +134,83 @@ impl TryMove for Player {
             return None;
         }
 
-        let new_pos = self.tile_pos + self.movement.direction;
-        if !map_data.is_valid_position(new_pos.x, new_pos.y)
-            || map_data.is_wall(new_pos.x as usize, new_pos.y as usize)
-        {
+        let new_pos = self.next_tile(map_data, self.movement.direction);
+        if new_pos.is_none() {
             self.movement.stop_moving();
-            return None;
         }
 
-        Some(new_pos)
+        new_pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{MAP_PATH, MapLoader, TextMapLoader};
+
+    #[test]
+    fn test_buffered_turn() -> anyhow::Result<()> {
+        let map_data = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut player = Player::new(7, 23);
+        player.movement.set_direction(IVec2::NEG_X);
+
+        // 上方是墙，期望方向先保留
+        player.intended_direction = IVec2::NEG_Y;
+        assert!(!player.try_turn(&map_data));
+        assert_eq!(player.movement.direction, IVec2::NEG_X);
+
+        // 到达路口后转向
+        player.tile_pos = IVec2::new(6, 23);
+        assert!(player.try_turn(&map_data));
+        assert_eq!(player.movement.direction, IVec2::NEG_Y);
+        Ok(())
+    }
+
+    #[test]
+    fn test_late_turn_stays_buffered() -> anyhow::Result<()> {
+        let map_data = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut player = Player::new(6, 23);
+        player.movement.set_direction(IVec2::NEG_X);
+        let interval = player.movement.get_move_interval();
+
+        // 已经离开路口大半格时按下，不会跳回路口，方向留到下一格
+        player.movement.accumulated_time = 0.6 * interval;
+        let before = player.position(&map_data, 0.0);
+        player.intended_direction = IVec2::NEG_Y;
+        assert!(!player.try_turn(&map_data));
+        assert_eq!(player.movement.direction, IVec2::NEG_X);
+        assert_eq!(player.position(&map_data, 0.0), before);
+        assert_eq!(player.intended_direction, IVec2::NEG_Y);
+
+        // 离路口很近时可以转向，位置只移动一点
+        player.movement.accumulated_time = 0.1 * interval;
+        let before = player.position(&map_data, 0.0);
+        assert!(player.try_turn(&map_data));
+        assert_eq!(player.movement.direction, IVec2::NEG_Y);
+        assert!(player.position(&map_data, 0.0).distance(before) <= 2.0 * player.pre_turn_window);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pre_turn() -> anyhow::Result<()> {
+        let map_data = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut player = Player::new(7, 23);
+        player.movement.set_direction(IVec2::NEG_X);
+        player.intended_direction = IVec2::NEG_Y;
+        let interval = player.movement.get_move_interval();
+
+        // 离下一个路口还远时不能提前转向
+        player.movement.accumulated_time = 0.5 * interval;
+        assert!(!player.can_pre_turn(&map_data));
+
+        player.movement.accumulated_time = 0.9 * interval;
+        assert!(player.can_pre_turn(&map_data));
+
+        // 下一格上方是墙时不提前转向
+        player.tile_pos = IVec2::new(8, 23);
+        assert!(!player.can_pre_turn(&map_data));
+        Ok(())
     }
 }
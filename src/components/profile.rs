@@ -0,0 +1,351 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use bevy::{ecs::resource::Resource, input::keyboard::KeyCode};
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    EXTRA_LIFE_SCORE, ExtraLifeRule, PROFILE_DIR, PROFILE_NAME_MAX_LEN, PROFILE_SETTINGS_FILE,
+    localization::Language, user_data_dir,
+};
+
+/// 难度，决定奖励生命的规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// 每 EXTRA_LIFE_SCORE 分奖励一条命
+    Easy,
+    /// 首次达到 EXTRA_LIFE_SCORE 分时奖励一条命
+    #[default]
+    Normal,
+    /// 不奖励生命
+    Hard,
+}
+
+impl Difficulty {
+    pub fn extra_life_rule(self) -> ExtraLifeRule {
+        match self {
+            Self::Easy => ExtraLifeRule::Every(EXTRA_LIFE_SCORE),
+            Self::Normal => ExtraLifeRule::Once(EXTRA_LIFE_SCORE),
+            Self::Hard => ExtraLifeRule::Disabled,
+        }
+    }
+
+    /// 菜单中依次切换
+    pub fn next(self) -> Self {
+        match self {
+            Self::Easy => Self::Normal,
+            Self::Normal => Self::Hard,
+            Self::Hard => Self::Easy,
+        }
+    }
+}
+
+/// 游戏模式，决定能否倒带
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PlayMode {
+    /// 休闲和练习，可以倒带，用过倒带的一局不计入最高分
+    #[default]
+    Casual,
+    /// 排位，不能倒带
+    Ranked,
+}
+
+impl PlayMode {
+    /// 是否可以倒带
+    pub fn allows_rewind(self) -> bool {
+        self == Self::Casual
+    }
+
+    /// 菜单中依次切换
+    pub fn next(self) -> Self {
+        match self {
+            Self::Casual => Self::Ranked,
+            Self::Ranked => Self::Casual,
+        }
+    }
+}
+
+/// 控制吃豆人移动的按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+}
+
+impl KeyBindings {
+    pub const WASD: Self = Self {
+        up: KeyCode::KeyW,
+        down: KeyCode::KeyS,
+        left: KeyCode::KeyA,
+        right: KeyCode::KeyD,
+    };
+
+    pub const ARROWS: Self = Self {
+        up: KeyCode::ArrowUp,
+        down: KeyCode::ArrowDown,
+        left: KeyCode::ArrowLeft,
+        right: KeyCode::ArrowRight,
+    };
+
+    /// 菜单中可以切换的预设，其他按键可以直接修改档案的设置文件
+    pub const PRESETS: [(&'static str, Self); 2] = [("WASD", Self::WASD), ("Arrows", Self::ARROWS)];
+
+    /// 按键对应的移动方向，不是方向键时为 None
+    pub fn direction(&self, key: KeyCode) -> Option<IVec2> {
+        if key == self.up {
+            Some(IVec2::NEG_Y)
+        } else if key == self.down {
+            Some(IVec2::Y)
+        } else if key == self.left {
+            Some(IVec2::NEG_X)
+        } else if key == self.right {
+            Some(IVec2::X)
+        } else {
+            None
+        }
+    }
+
+    /// 预设的名称，不是预设时为 None
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Self::PRESETS
+            .iter()
+            .find(|(_, preset)| preset == self)
+            .map(|(name, _)| *name)
+    }
+
+    /// 切换到下一个预设
+    pub fn next_preset(&self) -> Self {
+        let index = Self::PRESETS
+            .iter()
+            .position(|(_, preset)| preset == self)
+            .map_or(0, |index| (index + 1) % Self::PRESETS.len());
+        Self::PRESETS[index].1
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::WASD
+    }
+}
+
+/// 每个档案自己的设置
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSettings {
+    pub language: Language,
+    pub key_bindings: KeyBindings,
+    pub difficulty: Difficulty,
+    pub mode: PlayMode,
+}
+
+impl ProfileSettings {
+    /// 读取档案目录中的设置，没有设置文件时使用默认设置
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let path = dir.join(PROFILE_SETTINGS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read settings {}", path.display()))?;
+        ron::from_str(&text).with_context(|| format!("Failed to parse settings {}", path.display()))
+    }
+
+    /// 保存到档案目录
+    pub fn save(&self, dir: &Path) -> anyhow::Result<()> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(PROFILE_SETTINGS_FILE);
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(&path, text)
+            .with_context(|| format!("Failed to write settings {}", path.display()))
+    }
+}
+
+/// 一个玩家档案，设置、排行榜、统计、成就和存档都保存在档案目录中
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub name: String,
+    pub dir: PathBuf,
+    pub settings: ProfileSettings,
+}
+
+impl Profile {
+    /// 保存档案的设置
+    pub fn save_settings(&self) -> anyhow::Result<()> {
+        self.settings.save(&self.dir)
+    }
+}
+
+/// 当前选中的档案，启动后选择档案之前为 None
+#[derive(Resource, Default)]
+pub struct ActiveProfile(pub Option<Profile>);
+
+impl ActiveProfile {
+    /// 当前档案的目录，没有选中档案时不保存任何数据
+    pub fn dir(&self) -> Option<&Path> {
+        self.0.as_ref().map(|profile| profile.dir.as_path())
+    }
+}
+
+/// 所有档案，每个档案是根目录下以档案名命名的子目录
+#[derive(Debug, Clone)]
+pub struct ProfileStore {
+    root: PathBuf,
+}
+
+impl Default for ProfileStore {
+    /// 档案保存在用户数据目录中
+    fn default() -> Self {
+        Self::new(user_data_dir().join(PROFILE_DIR))
+    }
+}
+
+impl ProfileStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// 按名称排序的所有档案名
+    pub fn list(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// 读取档案
+    pub fn open(&self, name: &str) -> anyhow::Result<Profile> {
+        let dir = self.root.join(name);
+        if !dir.is_dir() {
+            bail!("Profile {:?} does not exist", name);
+        }
+        let settings = ProfileSettings::load(&dir)?;
+        Ok(Profile {
+            name: name.to_string(),
+            dir,
+            settings,
+        })
+    }
+
+    /// 创建新档案，使用默认设置
+    pub fn create(&self, name: &str) -> anyhow::Result<Profile> {
+        let dir = self.available_dir(name)?;
+        let profile = Profile {
+            name: name.to_string(),
+            dir,
+            settings: ProfileSettings::default(),
+        };
+        profile.save_settings()?;
+        Ok(profile)
+    }
+
+    /// 重命名档案，档案中的数据随目录一起移动
+    pub fn rename(&self, name: &str, new_name: &str) -> anyhow::Result<()> {
+        let new_dir = self.available_dir(new_name)?;
+        fs::rename(self.root.join(name), new_dir)
+            .with_context(|| format!("Failed to rename profile {:?}", name))
+    }
+
+    /// 删除档案及其所有数据
+    pub fn delete(&self, name: &str) -> anyhow::Result<()> {
+        fs::remove_dir_all(self.root.join(name))
+            .with_context(|| format!("Failed to delete profile {:?}", name))
+    }
+
+    /// 检查档案名并返回新档案的目录
+    fn available_dir(&self, name: &str) -> anyhow::Result<PathBuf> {
+        validate_profile_name(name)?;
+        let dir = self.root.join(name);
+        if dir.exists() {
+            bail!("Profile {:?} already exists", name);
+        }
+        Ok(dir)
+    }
+}
+
+/// 档案名直接作为目录名，只允许字母、数字、空格、'-' 和 '_'
+pub fn validate_profile_name(name: &str) -> anyhow::Result<()> {
+    if name.trim().is_empty() || name.trim() != name {
+        bail!("Profile name must not be empty or start or end with a space");
+    }
+    if name.chars().count() > PROFILE_NAME_MAX_LEN {
+        bail!(
+            "Profile name must be at most {} characters",
+            PROFILE_NAME_MAX_LEN
+        );
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+    {
+        bail!("Profile name may only contain letters, digits, spaces, '-' and '_'");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    #[test]
+    fn test_profile_store() -> anyhow::Result<()> {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let root = std::env::temp_dir().join(format!("pac-man-profiles-{}", nanos));
+        let store = ProfileStore::new(root.clone());
+        assert!(store.list().is_empty());
+
+        let mut profile = store.create("Bob")?;
+        profile.settings.difficulty = Difficulty::Hard;
+        profile.settings.key_bindings = KeyBindings::ARROWS;
+        profile.settings.mode = PlayMode::Ranked;
+        profile.save_settings()?;
+        store.create("alice")?;
+        assert!(store.create("Bob").is_err());
+        assert!(store.create("../evil").is_err());
+        assert!(store.create(" ").is_err());
+        assert_eq!(store.list(), vec!["Bob", "alice"]);
+
+        // 重命名后设置随档案一起移动
+        store.rename("Bob", "Carol")?;
+        assert!(store.rename("alice", "Carol").is_err());
+        assert_eq!(store.open("Carol")?.settings, profile.settings);
+
+        store.delete("alice")?;
+        assert_eq!(store.list(), vec!["Carol"]);
+        assert!(store.open("alice").is_err());
+
+        fs::remove_dir_all(root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_binding_presets() {
+        let keys = KeyBindings::default();
+        assert_eq!(keys.preset_name(), Some("WASD"));
+        assert_eq!(keys.direction(KeyCode::KeyW), Some(IVec2::NEG_Y));
+        assert_eq!(keys.direction(KeyCode::ArrowUp), None);
+        assert_eq!(keys.next_preset(), KeyBindings::ARROWS);
+        assert_eq!(KeyBindings::ARROWS.next_preset(), KeyBindings::WASD);
+
+        let custom = KeyBindings {
+            up: KeyCode::KeyI,
+            ..KeyBindings::WASD
+        };
+        assert_eq!(custom.preset_name(), None);
+        assert_eq!(custom.next_preset(), KeyBindings::WASD);
+    }
+}
@@ -1,4 +1,7 @@
 use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::GHOST_BASE_POINTS;
 
 /// 分数
 #[derive(Resource, Default)]
@@ -12,3 +15,60 @@ impl Score {
         self.value += 1;
     }
 }
+
+/// 当前地图的最高分，启动时从排行榜读取
+#[derive(Resource, Default)]
+pub struct BestScore {
+    pub value: u32,
+}
+
+impl BestScore {
+    /// 用本局分数刷新最高分
+    ///
+    /// ## Return
+    /// 若创造了新纪录则返回 true
+    pub fn update(&mut self, score: u32) -> bool {
+        if score > self.value {
+            self.value = score;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// 一颗能量豆内连续吃幽灵的连击
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GhostCombo {
+    /// 本次能量豆已吃掉的幽灵数
+    pub eaten: u32,
+}
+
+impl GhostCombo {
+    /// 记录吃掉一只幽灵，返回本次获得的分数：200 / 400 / 800 / 1600
+    pub fn next_points(&mut self) -> u32 {
+        let points = GHOST_BASE_POINTS << self.eaten.min(3);
+        self.eaten += 1;
+        points
+    }
+
+    /// 连击中断
+    pub fn reset(&mut self) {
+        self.eaten = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ghost_combo_points() {
+        let mut combo = GhostCombo::default();
+        let points: Vec<u32> = (0..5).map(|_| combo.next_points()).collect();
+        assert_eq!(points, vec![200, 400, 800, 1600, 1600]);
+
+        combo.reset();
+        assert_eq!(combo.next_points(), 200);
+    }
+}
@@ -4,8 +4,11 @@ use bevy::state::state::States;
 #[derive(States, Default, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum GameState {
     #[default]
-    Menu, // 主菜单
-    Playing,  // 游戏中
-    Paused,   // 暂停
-    GameOver, // 游戏结束
+    Profiles, // 选择档案
+    Menu,       // 主菜单
+    Playing,    // 游戏中
+    Paused,     // 暂停
+    GameOver,   // 游戏结束
+    HighScores, // 排行榜
+    Statistics, // 累计统计
 }
@@ -0,0 +1,132 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::{GhostType, PelletType, STATS_FILE};
+
+/// 跨多次运行累计的玩家统计
+///
+/// 新增字段使用默认值，旧的统计文件可以继续读取
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    /// 吃掉的普通豆子数
+    pub pellets_eaten: u64,
+    /// 吃掉的能量豆数
+    pub power_pellets_eaten: u64,
+    /// 每种幽灵被吃掉的次数
+    pub ghosts_eaten: BTreeMap<GhostType, u64>,
+    /// 被每种幽灵抓住的次数
+    pub deaths: BTreeMap<GhostType, u64>,
+    /// 完成的关卡数
+    pub levels_cleared: u64,
+    /// 最快通关一关的时间（秒）
+    pub fastest_level_clear: Option<f32>,
+    /// 累计游戏时间（秒）
+    pub play_time: f64,
+}
+
+impl LifetimeStats {
+    /// 记录吃掉一颗豆子
+    pub fn record_pellet(&mut self, pellet_type: PelletType) {
+        match pellet_type {
+            PelletType::General => self.pellets_eaten += 1,
+            PelletType::Power => self.power_pellets_eaten += 1,
+        }
+    }
+
+    /// 记录吃掉一只幽灵
+    pub fn record_ghost_eaten(&mut self, ghost_type: GhostType) {
+        *self.ghosts_eaten.entry(ghost_type).or_default() += 1;
+    }
+
+    /// 记录被幽灵抓住
+    pub fn record_death(&mut self, ghost_type: GhostType) {
+        *self.deaths.entry(ghost_type).or_default() += 1;
+    }
+
+    /// 记录完成一关
+    ///
+    /// ## Params
+    /// - duration: 这一关用的时间（秒），无法得知时为 None，不参与最快通关
+    pub fn record_level_cleared(&mut self, duration: Option<f32>) {
+        self.levels_cleared += 1;
+        if let Some(duration) = duration
+            && self
+                .fastest_level_clear
+                .is_none_or(|fastest| duration < fastest)
+        {
+            self.fastest_level_clear = Some(duration);
+        }
+    }
+
+    pub fn ghosts_eaten_by(&self, ghost_type: GhostType) -> u64 {
+        self.ghosts_eaten.get(&ghost_type).copied().unwrap_or(0)
+    }
+
+    pub fn deaths_by(&self, ghost_type: GhostType) -> u64 {
+        self.deaths.get(&ghost_type).copied().unwrap_or(0)
+    }
+
+    /// 统计文件，保存在档案目录中
+    pub fn path(profile_dir: &Path) -> PathBuf {
+        profile_dir.join(STATS_FILE)
+    }
+
+    /// 从文件读取统计
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read stats {}", path.display()))?;
+        ron::from_str(&text).with_context(|| format!("Failed to parse stats {}", path.display()))
+    }
+
+    /// 保存到文件，目录不存在时自动创建
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, text).with_context(|| format!("Failed to write stats {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifetime_stats() -> anyhow::Result<()> {
+        let mut stats = LifetimeStats::default();
+        stats.record_pellet(PelletType::General);
+        stats.record_pellet(PelletType::Power);
+        stats.record_ghost_eaten(GhostType::Inky);
+        stats.record_ghost_eaten(GhostType::Inky);
+        stats.record_death(GhostType::Blinky);
+        stats.record_level_cleared(Some(95.0));
+        stats.record_level_cleared(None);
+        stats.record_level_cleared(Some(120.0));
+
+        assert_eq!(stats.pellets_eaten, 1);
+        assert_eq!(stats.power_pellets_eaten, 1);
+        assert_eq!(stats.ghosts_eaten_by(GhostType::Inky), 2);
+        assert_eq!(stats.ghosts_eaten_by(GhostType::Clyde), 0);
+        assert_eq!(stats.deaths_by(GhostType::Blinky), 1);
+        assert_eq!(stats.levels_cleared, 3);
+        assert_eq!(stats.fastest_level_clear, Some(95.0));
+
+        // 缺少字段的旧统计文件也能读取
+        let loaded: LifetimeStats = ron::from_str("(pellets_eaten: 7)")?;
+        assert_eq!(loaded.pellets_eaten, 7);
+        assert_eq!(loaded.levels_cleared, 0);
+
+        let text = ron::to_string(&stats)?;
+        assert_eq!(ron::from_str::<LifetimeStats>(&text)?, stats);
+        Ok(())
+    }
+}
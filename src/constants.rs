@@ -3,7 +3,7 @@
 use bevy::color::Color;
 
 // 窗口参数
-pub const WINDOW_TITLE: &'static str = "Pac-Man";
+pub const WINDOW_TITLE: &str = "Pac-Man";
 pub const WINDOW_WIDTH: u32 = 960;
 pub const WINDOW_HEIGHT: u32 = 640;
 pub const BACKGROUND_COLOR: Color = Color::BLACK;
@@ -13,12 +13,55 @@ pub const TITLE_COLOR: Color = Color::srgb(1.0, 1.0, 0.0); // 黄色
 pub const BUTTON_TEXT_COLOR: Color = Color::WHITE;
 pub const START_BUTTON_BORDER_COLOR: Color = Color::srgb(0.2, 1.0, 0.2); // 亮绿色边框
 pub const QUIT_BUTTON_BORDER_COLOR: Color = Color::srgb(1.0, 0.3, 0.3); // 红色边框
+pub const RESTART_BUTTON_BORDER_COLOR: Color = Color::srgb(1.0, 1.0, 0.2); // 黄色边框
+pub const REPLAY_BUTTON_BORDER_COLOR: Color = Color::srgb(0.3, 0.8, 1.0); // 浅蓝色边框
+pub const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7); // 半透明遮罩
 pub const PRESSED_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
 pub const HOVERED_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
 pub const NONE_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
 
+// 模拟参数
+pub const SIMULATION_HZ: f64 = 60.0; // 游戏逻辑固定每秒运行的帧数，与渲染帧率无关
+
+// 回放参数
+pub const REPLAY_DIR: &str = "replays"; // 档案目录下每局结束后回放保存的目录
+pub const REPLAY_EXTENSION: &str = "replay";
+pub const PERSONAL_BEST_PREFIX: &str = "best-"; // 个人最佳回放的文件名前缀，后接地图名
+pub const GHOST_RUN_ALPHA: f32 = 0.35; // 个人最佳回放中吃豆人的透明度
+pub const GHOST_RUN_HUD_FONT_SIZE: f32 = 16.0;
+pub const GHOST_RUN_AHEAD_COLOR: Color = Color::srgb(0.3, 1.0, 0.3); // 领先时 HUD 为绿色
+pub const GHOST_RUN_BEHIND_COLOR: Color = Color::srgb(1.0, 0.3, 0.3); // 落后时 HUD 为红色
+
+// 档案参数
+pub const PROFILE_DIR: &str = "profiles"; // 用户数据目录下的档案目录，每个档案一个子目录
+pub const PROFILE_SETTINGS_FILE: &str = "settings.ron";
+pub const PROFILE_NAME_MAX_LEN: usize = 12;
+
+// 存档参数
+pub const SAVE_DIR: &str = "saves"; // 档案目录下的存档目录
+pub const SAVE_SLOTS: usize = 3; // 存档槽位数，每个槽位保存一局未完成的游戏
+
+// 排行榜参数
+pub const DATA_DIR_NAME: &str = "pac-man"; // 用户数据目录下的子目录名
+pub const HIGH_SCORE_DIR: &str = "high-scores"; // 档案目录下的排行榜目录，每张地图一个文件
+pub const HIGH_SCORE_COUNT: usize = 10;
+pub const INITIALS_LEN: usize = 3;
+pub const HUD_FONT_SIZE: f32 = 16.0;
+
+// 统计参数
+pub const STATS_FILE: &str = "stats.ron"; // 档案目录下的累计统计文件
+
+// 成就参数
+pub const ACHIEVEMENTS_PATH: &str = "assets/achievements.ron"; // 成就定义文件，修改后不需要重新编译
+pub const ACHIEVEMENT_UNLOCKS_FILE: &str = "achievements.ron"; // 档案目录下的解锁记录
+pub const ACHIEVEMENT_TOAST_DURATION: f32 = 3.0;
+pub const ACHIEVEMENT_TOAST_COLOR: Color = Color::srgba(0.1, 0.1, 0.3, 0.9);
+
+// 倒带参数
+pub const REWIND_SECONDS: f64 = 10.0; // 最多能倒回的游戏时间
+
 // 地图参数
-pub const MAP_PATH: &'static str = "assets/map/pacman.map";
+pub const MAP_PATH: &str = "assets/map/pacman.map";
 pub const TILE_SIZE: f32 = 20.0;
 pub const HALF: f32 = TILE_SIZE / 2.0;
 pub const WALL_THICKNESS: f32 = 1.0;
@@ -36,15 +79,41 @@ pub const POWER_PELLET_POSITIONS: [(usize, usize); 4] = [
     (26, 23), // 右下角
 ];
 
+// 奖励水果参数
+pub const FRUIT_RADIUS: f32 = 6.0;
+pub const FRUIT_DURATION: f32 = 9.5;
+pub const FRUIT_PELLET_THRESHOLDS: [u32; 2] = [70, 170]; // 吃掉这么多豆子后出现水果
+pub const CHERRY_COLOR: Color = Color::srgb(0.9, 0.0, 0.1);
+pub const STRAWBERRY_COLOR: Color = Color::srgb(1.0, 0.2, 0.4);
+pub const ORANGE_COLOR: Color = Color::srgb(1.0, 0.6, 0.0);
+pub const APPLE_COLOR: Color = Color::srgb(0.8, 0.1, 0.1);
+pub const MELON_COLOR: Color = Color::srgb(0.3, 0.8, 0.3);
+pub const GALAXIAN_COLOR: Color = Color::srgb(1.0, 1.0, 0.3);
+pub const BELL_COLOR: Color = Color::srgb(1.0, 0.9, 0.2);
+pub const KEY_COLOR: Color = Color::srgb(0.6, 0.9, 1.0);
+
 // 玩家参数
 pub const PLAYER_RADIUS: f32 = 6.0;
+pub const INITIAL_LIVES: u32 = 3;
+pub const MAX_SPEED: f32 = 7.5; // 100% 速度（格/秒），各关卡速度按比例计算
+pub const EXTRA_LIFE_SCORE: u32 = 10_000; // 达到该分数奖励一条命
+pub const COLLISION_DISTANCE: f32 = 0.5; // 玩家与幽灵的距离（格）小于该值时相撞
+pub const PLAYER_PRE_TURN_WINDOW: f32 = 0.15; // 离格子中心多近时可以拐弯（格），为 0 时不能提前转向
 pub const PLAYER_COLOR: Color = TITLE_COLOR;
 
 // 渲染Z轴
 pub const Z_MAP: f32 = 0.0;
 pub const Z_WALL: f32 = 1.0;
 pub const Z_PELLET: f32 = 2.0;
+pub const Z_FRUIT: f32 = 2.5;
+pub const Z_GHOST_RUN: f32 = 2.9;
 pub const Z_PLAYER: f32 = 3.0;
+pub const Z_POPUP: f32 = 5.0;
+
+// 得分提示参数
+pub const POPUP_DURATION: f32 = 1.0;
+pub const POPUP_FONT_SIZE: f32 = 12.0;
+pub const POPUP_COLOR: Color = Color::srgb(0.0, 1.0, 1.0);
 
 // 幽灵参数
 pub const GHOST_RADIUS: f32 = 8.0;
@@ -64,4 +133,10 @@ pub const GHOST_STROKE_COLOR: Color = Color::srgb(0.0, 0.0, 0.0);
 pub const Z_GHOST_BODY: f32 = 3.0;
 pub const Z_GHOST_EYE_WHITE: f32 = 3.1;
 pub const Z_GHOST_EYE_PUPIL: f32 = 3.2;
-pub const GHOST_FRIGHTENED_FLASH_INTERVAL: f32 = 0.1;
+pub const GHOST_FRIGHTENED_FLASH_INTERVAL: f32 = 0.2; // 闪烁时蓝白各持续的时间
+pub const GHOST_RETURN_SPEED: f32 = 8.0; // 被吃掉后返回基地的速度
+pub const GHOST_BASE_POINTS: u32 = 200; // 连击中第一只幽灵的分数，之后每只翻倍
+pub const HOUSE_DOOR_COLOR: Color = Color::srgb(1.0, 0.72, 0.87);
+pub const HOUSE_DOOR_THICKNESS: f32 = 2.0;
+pub const GHOST_HOUSE_IDLE_LIMIT: f32 = 4.0; // 超过该时间没吃豆子则强制放出下一只幽灵
+pub const GHOST_HOUSE_GLOBAL_DOT_LIMITS: [u32; 4] = [0, 7, 17, 32]; // 失去生命后全局豆子计数的放出阈值
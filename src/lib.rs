@@ -1,7 +1,9 @@
 mod components;
 mod constants;
+mod simulation;
 mod systems;
 
 pub use components::*;
 pub use constants::*;
+pub use simulation::*;
 pub use systems::*;
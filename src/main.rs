@@ -1,11 +1,31 @@
 use bevy::prelude::*;
 use bevy_prototype_lyon::plugin::ShapePlugin;
 use pac_man::{
-    BACKGROUND_COLOR, EatPelletEvent, FontAssets, GameState, LanguageSettings, Score,
-    WINDOW_HEIGHT, WINDOW_TITLE, WINDOW_WIDTH, cleanup_menu_ui, ghost_ai_system, ghost_move_system,
-    handle_eat_pellet_message, handle_menu_button, handle_player_input, load_font_assets,
-    load_map_data, player_update, setup_map_ui, setup_menu_ui, spawn_ghost_ui, sync_player_ui,
-    update_ghost_ui,
+    AchievementProgress, AchievementUnlockedEvent, Achievements, ActiveProfile, BACKGROUND_COLOR,
+    BestScore, EatPelletEvent, ExtraLifeEvent, FontAssets, FruitEatenEvent, GameSimulation,
+    GameState, GhostEatenEvent, GhostRun, GhostRunSettings, HighScores, InitialsEntry,
+    LanguageSettings, Level, LevelClearedEvent, LifetimeStats, Lives, PersonalBest,
+    PlayerDeathEvent, PlayerInput, ProfileMenu, ReplayPlayback, ReplayRecorder, Rewind,
+    SIMULATION_HZ, SaveSlotMenu, SaveSlots, Score, StatsTracker, WINDOW_HEIGHT, WINDOW_TITLE,
+    WINDOW_WIDTH, apply_difficulty, cleanup_game_over_ui, cleanup_high_scores_ui, cleanup_menu_ui,
+    cleanup_pause_ui, cleanup_profiles_ui, cleanup_statistics_ui, clear_save_slot, end_replay,
+    finish_initials_entry, handle_back_button, handle_eat_pellet_message, handle_game_over_button,
+    handle_initials_input, handle_level_cleared_message, handle_menu_button, handle_pause_button,
+    handle_profile_name_input, handle_profile_setting_button, handle_profiles_button,
+    handle_save_slot_button, load_achievement_unlocks, load_achievements, load_font_assets,
+    load_high_scores, load_map_data, load_personal_best, load_replay_from_args, load_save_slots,
+    load_stats, pause_game_time, pause_on_focus_lost, play_replay_input, read_player_input,
+    read_rewind_input, record_rewind_snapshot, record_stats, refresh_profile_menu, replay_finished,
+    reset_game, reset_rewind, reset_save_slot_menu, resume_game_time, rewind_simulation,
+    rewind_unused, rewinding, save_game, save_on_window_close, save_replay, save_stats,
+    save_stats_on_window_close, setup_game_over_ui, setup_high_scores_ui, setup_menu_ui,
+    setup_pause_ui, setup_profiles_ui, setup_statistics_ui, spawn_achievement_toasts,
+    spawn_ghost_run_ui, spawn_ghost_ui, spawn_score_hud, spawn_score_popups, start_game_systems,
+    start_initials_entry, step_ghost_run, step_simulation, sync_fruit_ui, sync_player_ui,
+    sync_simulation, toggle_ghost_run, toggle_pause, track_achievements, track_play_time,
+    update_achievement_toasts, update_best_score, update_ghost_run_hud, update_ghost_run_ui,
+    update_ghost_ui, update_initials_ui, update_profile_setting_texts, update_score_hud,
+    update_score_popups,
 };
 
 fn main() {
@@ -23,37 +43,271 @@ fn main() {
         .init_resource::<LanguageSettings>()
         .init_resource::<FontAssets>()
         .insert_resource(Score { value: 0 })
+        .init_resource::<Lives>()
+        .init_resource::<Level>()
+        .init_resource::<BestScore>()
+        .init_resource::<GameSimulation>()
+        .init_resource::<PlayerInput>()
+        .init_resource::<ReplayRecorder>()
+        .init_resource::<PersonalBest>()
+        .init_resource::<GhostRunSettings>()
+        .init_resource::<Rewind>()
+        .init_resource::<SaveSlots>()
+        .init_resource::<SaveSlotMenu>()
+        .init_resource::<HighScores>()
+        .init_resource::<LifetimeStats>()
+        .init_resource::<StatsTracker>()
+        .init_resource::<Achievements>()
+        .init_resource::<ActiveProfile>()
+        .init_resource::<ProfileMenu>()
+        .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
         .insert_resource(ClearColor(BACKGROUND_COLOR))
         .add_message::<EatPelletEvent>()
-        .add_systems(Startup, (load_font_assets, setup_camera, load_map_data))
+        .add_message::<FruitEatenEvent>()
+        .add_message::<GhostEatenEvent>()
+        .add_message::<PlayerDeathEvent>()
+        .add_message::<ExtraLifeEvent>()
+        .add_message::<LevelClearedEvent>()
+        .add_message::<AchievementUnlockedEvent>()
+        .add_systems(
+            Startup,
+            (
+                load_font_assets,
+                setup_camera,
+                load_replay_from_args,
+                load_achievements,
+                load_map_data.after(load_replay_from_args),
+            ),
+        )
+        // 档案系统，选择档案后读取该档案的存档、排行榜、统计和成就
+        .add_systems(OnEnter(GameState::Profiles), refresh_profile_menu)
+        .add_systems(
+            Update,
+            (
+                handle_profiles_button,
+                handle_profile_name_input,
+                setup_profiles_ui
+                    .after(handle_profiles_button)
+                    .after(handle_profile_name_input)
+                    .run_if(resource_changed::<ProfileMenu>),
+            )
+                .run_if(in_state(GameState::Profiles)),
+        )
+        .add_systems(OnExit(GameState::Profiles), cleanup_profiles_ui)
+        .add_systems(
+            OnTransition {
+                exited: GameState::Profiles,
+                entered: GameState::Menu,
+            },
+            (
+                load_save_slots,
+                load_high_scores,
+                load_stats,
+                load_achievement_unlocks,
+                load_personal_best,
+            ),
+        )
         // 菜单系统
         .add_systems(OnEnter(GameState::Menu), setup_menu_ui)
-        .add_systems(Update, handle_menu_button.run_if(in_state(GameState::Menu)))
-        .add_systems(OnExit(GameState::Menu), cleanup_menu_ui)
-        // 地图系统
-        .add_systems(OnEnter(GameState::Playing), setup_map_ui)
-        // 玩家系统
         .add_systems(
             Update,
             (
-                handle_player_input,
-                player_update.after(handle_player_input),
-                handle_eat_pellet_message.after(player_update),
-                sync_player_ui,
+                handle_menu_button,
+                handle_save_slot_button,
+                // 存档槽位的确认或删除后重新生成菜单
+                (cleanup_menu_ui, setup_menu_ui)
+                    .chain()
+                    .after(handle_save_slot_button)
+                    .run_if(resource_changed::<SaveSlotMenu>),
+                handle_profile_setting_button,
+                update_profile_setting_texts.run_if(resource_changed::<ActiveProfile>),
+            )
+                .run_if(in_state(GameState::Menu)),
+        )
+        .add_systems(
+            OnExit(GameState::Menu),
+            (cleanup_menu_ui, reset_save_slot_menu),
+        )
+        // 排行榜和统计页面
+        .add_systems(OnEnter(GameState::HighScores), setup_high_scores_ui)
+        .add_systems(OnExit(GameState::HighScores), cleanup_high_scores_ui)
+        .add_systems(OnEnter(GameState::Statistics), setup_statistics_ui)
+        .add_systems(OnExit(GameState::Statistics), cleanup_statistics_ui)
+        .add_systems(
+            Update,
+            handle_back_button
+                .run_if(in_state(GameState::HighScores).or(in_state(GameState::Statistics))),
+        )
+        // 地图系统，从暂停恢复时不重新生成，新的一局开始录制回放
+        .add_systems(
+            OnTransition {
+                exited: GameState::Menu,
+                entered: GameState::Playing,
+            },
+            start_game_systems(),
+        )
+        .add_systems(
+            OnTransition {
+                exited: GameState::GameOver,
+                entered: GameState::Playing,
+            },
+            (start_game_systems(), reset_rewind),
+        )
+        // 命令行回放从档案页面直接进入游戏
+        .add_systems(
+            OnTransition {
+                exited: GameState::Profiles,
+                entered: GameState::Playing,
+            },
+            start_game_systems(),
+        )
+        // 关闭窗口时自动存档并保存统计
+        .add_systems(Update, (save_on_window_close, save_stats_on_window_close))
+        // 暂停系统
+        .add_systems(
+            Update,
+            (
+                toggle_pause,
+                pause_on_focus_lost.run_if(in_state(GameState::Playing)),
+                handle_pause_button.run_if(in_state(GameState::Paused)),
+            )
+                .run_if(in_state(GameState::Playing).or(in_state(GameState::Paused))),
+        )
+        .add_systems(
+            OnEnter(GameState::Paused),
+            (setup_pause_ui, pause_game_time),
+        )
+        .add_systems(
+            OnExit(GameState::Paused),
+            (cleanup_pause_ui, resume_game_time),
+        )
+        .add_systems(
+            OnTransition {
+                exited: GameState::Paused,
+                entered: GameState::Menu,
+            },
+            (
+                save_replay,
+                save_game,
+                save_stats,
+                reset_game.after(save_replay).after(save_game),
+                apply_difficulty.after(reset_game),
+            ),
+        )
+        // 游戏结束系统
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            (
+                save_replay,
+                clear_save_slot,
+                save_stats,
+                // 观看回放和用过倒带的一局不刷新最高分，也不能上榜
+                (update_best_score, start_initials_entry)
+                    .run_if(not(resource_exists::<ReplayPlayback>))
+                    .run_if(rewind_unused),
+                setup_game_over_ui
+                    .after(update_best_score)
+                    .after(start_initials_entry),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_game_over_button,
+                (
+                    handle_initials_input,
+                    update_initials_ui.after(handle_initials_input),
+                )
+                    .run_if(resource_exists::<InitialsEntry>),
+            )
+                .run_if(in_state(GameState::GameOver)),
+        )
+        .add_systems(
+            OnExit(GameState::GameOver),
+            (
+                cleanup_game_over_ui,
+                finish_initials_entry,
+                reset_game,
+                apply_difficulty.after(reset_game),
+            ),
+        )
+        // 玩家输入在渲染帧中采集，在模拟帧中处理；播放回放时输入来自回放
+        .add_systems(
+            RunFixedMainLoop,
+            (
+                read_player_input.run_if(not(resource_exists::<ReplayPlayback>)),
+                read_rewind_input,
+            )
+                .in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop)
+                .run_if(in_state(GameState::Playing)),
+        )
+        // 模拟系统：游戏逻辑都在 GameSimulation 中，这里只负责同步到实体和资源
+        // 倒带时不推进模拟，改为逐帧恢复快照
+        .add_systems(
+            FixedUpdate,
+            (
+                record_rewind_snapshot
+                    .before(step_simulation)
+                    .run_if(not(rewinding)),
+                rewind_simulation
+                    .before(sync_simulation)
+                    .before(sync_fruit_ui)
+                    .run_if(rewinding),
+                play_replay_input
+                    .before(step_simulation)
+                    .run_if(resource_exists::<ReplayPlayback>)
+                    .run_if(not(rewinding)),
+                step_simulation
+                    .run_if(not(replay_finished))
+                    .run_if(not(rewinding)),
+                end_replay.run_if(replay_finished),
+                sync_simulation.after(step_simulation),
+                handle_eat_pellet_message.after(step_simulation),
+                handle_level_cleared_message.after(handle_eat_pellet_message),
+                sync_fruit_ui.after(handle_level_cleared_message),
+                // 观看回放时不统计，用过倒带后不再累计重玩部分的统计
+                (
+                    record_stats.after(step_simulation).run_if(rewind_unused),
+                    track_play_time,
+                )
+                    .run_if(not(resource_exists::<ReplayPlayback>)),
+                // 观看回放和用过倒带的一局不解锁成就
+                track_achievements
+                    .after(step_simulation)
+                    .run_if(resource_exists::<AchievementProgress>)
+                    .run_if(not(resource_exists::<ReplayPlayback>))
+                    .run_if(rewind_unused),
+                step_ghost_run
+                    .after(step_simulation)
+                    .run_if(resource_exists::<GhostRun>)
+                    .run_if(not(rewinding)),
             )
                 .run_if(in_state(GameState::Playing)),
         )
-        // 幽灵系统
+        // 渲染系统
         .add_systems(
             Update,
             (
-                ghost_ai_system,
-                ghost_move_system.after(ghost_ai_system),
+                sync_player_ui,
                 spawn_ghost_ui,
-                update_ghost_ui.after(ghost_move_system),
+                update_ghost_ui,
+                spawn_score_popups,
+                update_score_popups,
+                toggle_ghost_run,
+                spawn_score_hud,
+                update_score_hud.after(spawn_score_hud),
+                spawn_ghost_run_ui,
+                (update_ghost_run_ui, update_ghost_run_hud)
+                    .after(spawn_ghost_run_ui)
+                    .run_if(resource_exists::<GhostRun>),
             )
                 .run_if(in_state(GameState::Playing)),
         )
+        // 成就提示在任何状态下都显示到计时结束
+        .add_systems(
+            Update,
+            (spawn_achievement_toasts, update_achievement_toasts),
+        )
         .run();
 }
 
@@ -0,0 +1,92 @@
+use glam::{IVec2, Vec2};
+
+use crate::{COLLISION_DISTANCE, MapData};
+
+/// 判断两个角色的实际位置是否相撞，隧道两端的角色也能相撞
+///
+/// ## Params
+/// - map_data: 地图数据
+/// - player_pos: 玩家的连续坐标（单位：格）
+/// - ghost_pos: 幽灵的连续坐标（单位：格）
+pub fn is_colliding(map_data: &MapData, player_pos: Vec2, ghost_pos: Vec2) -> bool {
+    map_data.wrapped_euclidean_distance(player_pos, ghost_pos) < COLLISION_DISTANCE
+}
+
+/// 判断两个角色是否在一帧内互换了格子（擦肩而过）
+///
+/// 帧率很低时一帧可能走过不止半格，距离判断会漏掉这种情况
+pub fn is_swapped(
+    player_prev: IVec2,
+    player_now: IVec2,
+    ghost_prev: IVec2,
+    ghost_now: IVec2,
+) -> bool {
+    player_prev != player_now && player_now == ghost_prev && player_prev == ghost_now
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{MAP_PATH, MapLoader, TextMapLoader};
+
+    #[test]
+    fn test_distance_collision() -> anyhow::Result<()> {
+        let map_data = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        assert!(is_colliding(
+            &map_data,
+            Vec2::new(5.0, 5.0),
+            Vec2::new(5.3, 5.0)
+        ));
+        assert!(is_colliding(
+            &map_data,
+            Vec2::new(4.8, 5.0),
+            Vec2::new(5.0, 5.1)
+        ));
+        assert!(!is_colliding(
+            &map_data,
+            Vec2::new(4.0, 5.0),
+            Vec2::new(5.0, 5.0)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tunnel_edge_collision() -> anyhow::Result<()> {
+        let map_data = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        // 第14行隧道的两端
+        assert!(is_colliding(
+            &map_data,
+            Vec2::new(27.8, 14.0),
+            Vec2::new(0.1, 14.0)
+        ));
+        assert!(is_colliding(
+            &map_data,
+            Vec2::new(-0.3, 14.0),
+            Vec2::new(27.9, 14.0)
+        ));
+        assert!(!is_colliding(
+            &map_data,
+            Vec2::new(27.0, 14.0),
+            Vec2::new(0.5, 14.0)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_swap_collision() {
+        let a = IVec2::new(4, 5);
+        let b = IVec2::new(5, 5);
+        assert!(is_swapped(a, b, b, a));
+    }
+
+    #[test]
+    fn test_no_swap() {
+        let a = IVec2::new(4, 5);
+        let b = IVec2::new(5, 5);
+        let c = IVec2::new(6, 5);
+        assert!(!is_swapped(a, b, c, c));
+        assert!(!is_swapped(a, a, b, c));
+    }
+}
@@ -0,0 +1,15 @@
+//! 与 Bevy 无关的游戏逻辑，可以脱离 App 单独运行
+
+mod collision;
+mod replay;
+mod rewind;
+mod save;
+mod sim;
+mod sim_event;
+
+pub use collision::*;
+pub use replay::*;
+pub use rewind::*;
+pub use save::*;
+pub use sim::*;
+pub use sim_event::*;
@@ -0,0 +1,348 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{Context, bail};
+use glam::IVec2;
+
+use crate::{
+    ExtraLifeRule, MapLoader, SIMULATION_HZ, SimEvent, SimInput, Simulation, TextMapLoader,
+};
+
+/// 回放文件第一行，格式变化时增加版本号
+const REPLAY_HEADER: &str = "pac-man replay v1";
+
+/// 回放中记录的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayAction {
+    /// 按下方向键
+    Turn(IVec2),
+    /// 在暂停菜单中重玩本关
+    RestartLevel,
+}
+
+/// 一局游戏的回放：地图、种子、规则和每个模拟帧的操作
+///
+/// 模拟是确定性的，只要记录这些就能完整重现一局游戏。
+/// 大部分帧没有输入，所以只记录有操作的帧。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    /// 地图文件路径
+    pub map_path: PathBuf,
+    /// 随机数种子
+    pub seed: u64,
+    /// 奖励生命规则
+    pub extra_life: ExtraLifeRule,
+    /// 录制的总帧数
+    pub ticks: u64,
+    /// 按帧排序的操作
+    actions: Vec<(u64, ReplayAction)>,
+}
+
+impl Replay {
+    pub fn new(map_path: impl Into<PathBuf>, seed: u64, extra_life: ExtraLifeRule) -> Self {
+        Self {
+            map_path: map_path.into(),
+            seed,
+            extra_life,
+            ticks: 0,
+            actions: Vec::new(),
+        }
+    }
+
+    /// 记录一个模拟帧的输入
+    ///
+    /// ## Params
+    /// - tick: 执行这次输入之前模拟已经运行的帧数
+    /// - input: 本帧的输入
+    pub fn record_input(&mut self, tick: u64, input: SimInput) {
+        if let Some(dir) = input.direction {
+            self.actions.push((tick, ReplayAction::Turn(dir)));
+        }
+        self.ticks = self.ticks.max(tick + 1);
+    }
+
+    /// 记录在指定帧之前重玩了本关
+    pub fn record_restart(&mut self, tick: u64) {
+        self.actions.push((tick, ReplayAction::RestartLevel));
+    }
+
+    /// 倒带后丢弃指定帧及之后的输入，该帧之前执行的重玩本关保留
+    ///
+    /// ## Params
+    /// - tick: 倒回到的模拟帧
+    pub fn truncate(&mut self, tick: u64) {
+        self.actions.retain(|&(t, action)| match action {
+            ReplayAction::Turn(_) => t < tick,
+            ReplayAction::RestartLevel => t <= tick,
+        });
+        self.ticks = self.ticks.min(tick);
+    }
+
+    /// 指定帧的所有操作
+    pub fn actions_at(&self, tick: u64) -> impl Iterator<Item = ReplayAction> + '_ {
+        let start = self.actions.partition_point(|&(t, _)| t < tick);
+        self.actions[start..]
+            .iter()
+            .take_while(move |&&(t, _)| t == tick)
+            .map(|&(_, action)| action)
+    }
+
+    /// 加载回放中的地图，创建开局时的模拟
+    pub fn simulation(&self) -> anyhow::Result<Simulation> {
+        let map = TextMapLoader
+            .load_map(&self.map_path)
+            .with_context(|| format!("Failed to load map {}", self.map_path.display()))?;
+        Ok(Simulation::new(map, self.seed).with_extra_life_rule(self.extra_life))
+    }
+
+    /// 回放是否已经播放完
+    pub fn is_finished(&self, sim: &Simulation) -> bool {
+        sim.tick() >= self.ticks || sim.is_game_over()
+    }
+
+    /// 执行回放中本帧的操作，推进一个模拟帧
+    ///
+    /// ## Params
+    /// - sim: 由 simulation 创建、一直按回放推进的模拟
+    ///
+    /// ## Return
+    /// 本帧发生的事件
+    pub fn step(&self, sim: &mut Simulation) -> Vec<SimEvent> {
+        let mut input = SimInput::default();
+        for action in self.actions_at(sim.tick()) {
+            match action {
+                ReplayAction::Turn(dir) => input.direction = Some(dir),
+                ReplayAction::RestartLevel => sim.restart_level(),
+            }
+        }
+        sim.step(input)
+    }
+
+    /// 不渲染，直接把整局回放运行到结束
+    pub fn run(&self) -> anyhow::Result<Simulation> {
+        let mut sim = self.simulation()?;
+        while !self.is_finished(&sim) {
+            self.step(&mut sim);
+        }
+        Ok(sim)
+    }
+
+    /// 从文件读取回放
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read replay {}", path.display()))?
+            .parse()
+    }
+
+    /// 保存回放到文件，目录不存在时自动创建
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+            .with_context(|| format!("Failed to write replay {}", path.display()))
+    }
+}
+
+/// 回放的文本格式
+///
+/// ```text
+/// pac-man replay v1
+/// map assets/map/pacman.map
+/// seed 42
+/// hz 60
+/// extra_life once 10000
+/// ticks 3600
+/// 45 L
+/// 1200 restart
+/// ```
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", REPLAY_HEADER)?;
+        writeln!(f, "map {}", self.map_path.display())?;
+        writeln!(f, "seed {}", self.seed)?;
+        writeln!(f, "hz {}", SIMULATION_HZ)?;
+        match self.extra_life {
+            ExtraLifeRule::Disabled => writeln!(f, "extra_life disabled")?,
+            ExtraLifeRule::Once(score) => writeln!(f, "extra_life once {}", score)?,
+            ExtraLifeRule::Every(score) => writeln!(f, "extra_life every {}", score)?,
+        }
+        writeln!(f, "ticks {}", self.ticks)?;
+
+        for &(tick, action) in &self.actions {
+            let name = match action {
+                ReplayAction::Turn(IVec2::NEG_X) => "L",
+                ReplayAction::Turn(IVec2::X) => "R",
+                ReplayAction::Turn(IVec2::NEG_Y) => "U",
+                ReplayAction::Turn(IVec2::Y) => "D",
+                ReplayAction::Turn(_) => continue,
+                ReplayAction::RestartLevel => "restart",
+            };
+            writeln!(f, "{} {}", tick, name)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Replay {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+        if lines.next() != Some(REPLAY_HEADER) {
+            bail!("Not a replay file or unsupported version");
+        }
+
+        // 读取一行 "key value"
+        let mut field = |key: &str| -> anyhow::Result<&str> {
+            lines
+                .next()
+                .and_then(|line| line.strip_prefix(key)?.strip_prefix(' '))
+                .with_context(|| format!("Missing replay field `{}`", key))
+        };
+
+        let map_path = PathBuf::from(field("map")?);
+        let seed = field("seed")?.parse()?;
+        let hz: f64 = field("hz")?.parse()?;
+        if hz != SIMULATION_HZ {
+            bail!(
+                "Replay recorded at {} Hz, expected {} Hz",
+                hz,
+                SIMULATION_HZ
+            );
+        }
+        let extra_life = match field("extra_life")? {
+            "disabled" => ExtraLifeRule::Disabled,
+            rule => match rule.split_once(' ') {
+                Some(("once", score)) => ExtraLifeRule::Once(score.parse()?),
+                Some(("every", score)) => ExtraLifeRule::Every(score.parse()?),
+                _ => bail!("Unknown extra life rule `{}`", rule),
+            },
+        };
+        let ticks = field("ticks")?.parse()?;
+
+        let mut actions = Vec::new();
+        for line in lines {
+            let (tick, name) = line
+                .split_once(' ')
+                .with_context(|| format!("Invalid replay line `{}`", line))?;
+            let action = match name {
+                "L" => ReplayAction::Turn(IVec2::NEG_X),
+                "R" => ReplayAction::Turn(IVec2::X),
+                "U" => ReplayAction::Turn(IVec2::NEG_Y),
+                "D" => ReplayAction::Turn(IVec2::Y),
+                "restart" => ReplayAction::RestartLevel,
+                _ => bail!("Unknown replay action `{}`", name),
+            };
+            actions.push((tick.parse()?, action));
+        }
+        if !actions.is_sorted_by_key(|&(tick, _)| tick) {
+            bail!("Replay actions are out of order");
+        }
+
+        Ok(Self {
+            map_path,
+            seed,
+            extra_life,
+            ticks,
+            actions,
+        })
+    }
+}
+
+/// 从命令行参数中读取回放文件路径，支持 `--replay a.replay` 和 `--replay=a.replay`
+pub fn replay_path_from_args(args: impl IntoIterator<Item = String>) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--replay" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(value) = arg.strip_prefix("--replay=") {
+            return Some(PathBuf::from(value));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAP_PATH;
+
+    #[test]
+    fn test_replay_round_trip() -> anyhow::Result<()> {
+        let mut replay = Replay::new(MAP_PATH, 11, ExtraLifeRule::Every(500));
+        let mut sim = replay.simulation()?;
+        const DIRS: [IVec2; 4] = [IVec2::NEG_X, IVec2::NEG_Y, IVec2::X, IVec2::Y];
+
+        // 录制：按固定规律转向，中途重玩一次本关
+        for tick in 0..2400u64 {
+            if tick == 1500 {
+                replay.record_restart(sim.tick());
+                sim.restart_level();
+            }
+            let input = SimInput {
+                direction: tick
+                    .is_multiple_of(40)
+                    .then(|| DIRS[(tick / 40 % 4) as usize]),
+            };
+            replay.record_input(sim.tick(), input);
+            sim.step(input);
+        }
+
+        let parsed: Replay = replay.to_string().parse()?;
+        assert_eq!(parsed, replay);
+
+        // 回放得到完全相同的结果
+        let playback = parsed.run()?;
+        assert_eq!(playback.score(), sim.score());
+        assert_eq!(playback.pellets_eaten(), sim.pellets_eaten());
+        assert_eq!(playback.player().tile_pos, sim.player().tile_pos);
+        for (a, b) in playback.ghosts().iter().zip(sim.ghosts()) {
+            assert_eq!(a.tile_pos, b.tile_pos);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay_truncate() {
+        let mut replay = Replay::new(MAP_PATH, 1, ExtraLifeRule::default());
+        let left = SimInput {
+            direction: Some(IVec2::NEG_X),
+        };
+        replay.record_input(9, left);
+        replay.record_restart(10);
+        replay.record_input(10, left);
+        replay.record_input(11, left);
+
+        // 倒回第 10 帧：保留该帧之前的重玩，丢弃该帧的输入
+        replay.truncate(10);
+        assert_eq!(replay.ticks, 10);
+        assert_eq!(
+            replay.actions_at(10).collect::<Vec<_>>(),
+            vec![ReplayAction::RestartLevel]
+        );
+        assert_eq!(replay.actions_at(11).count(), 0);
+        assert_eq!(replay.actions_at(9).count(), 1);
+    }
+
+    #[test]
+    fn test_replay_path_from_args() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            replay_path_from_args(args(&["pac-man", "--replay", "a.replay"])),
+            Some(PathBuf::from("a.replay"))
+        );
+        assert_eq!(
+            replay_path_from_args(args(&["pac-man", "--replay=b.replay"])),
+            Some(PathBuf::from("b.replay"))
+        );
+        assert_eq!(
+            replay_path_from_args(args(&["pac-man", "--seed", "1"])),
+            None
+        );
+    }
+}
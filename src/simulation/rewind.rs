@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+
+use crate::{REWIND_SECONDS, SIMULATION_HZ};
+
+/// 倒带用的环形缓冲区，保存最近一段时间每个模拟帧的快照
+///
+/// 装满后丢弃最早的快照
+#[derive(Debug, Clone)]
+pub struct RewindBuffer<T> {
+    snapshots: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> Default for RewindBuffer<T> {
+    /// 保存最近 REWIND_SECONDS 秒的模拟帧
+    fn default() -> Self {
+        Self::new((REWIND_SECONDS * SIMULATION_HZ) as usize)
+    }
+}
+
+impl<T> RewindBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// 保存一个快照
+    pub fn push(&mut self, snapshot: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// 取出最近的快照
+    ///
+    /// ## Return
+    /// 已经倒回最早的快照时返回 None
+    pub fn pop(&mut self) -> Option<T> {
+        self.snapshots.pop_back()
+    }
+
+    /// 清空所有快照，新的一局开始时使用
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{MAP_PATH, MapLoader, SimInput, Simulation, TextMapLoader};
+
+    #[test]
+    fn test_rewind_buffer() -> anyhow::Result<()> {
+        let map = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut sim = Simulation::new(map, 5);
+        let mut buffer = RewindBuffer::new(100);
+
+        let input = SimInput {
+            direction: Some(glam::IVec2::NEG_X),
+        };
+        for _ in 0..300 {
+            buffer.push(sim.snapshot());
+            sim.step(input);
+        }
+        // 只保留最近 100 帧
+        assert_eq!(buffer.len(), 100);
+
+        let score = sim.score();
+        let player = sim.player().tile_pos;
+        while let Some(snapshot) = buffer.pop() {
+            sim.restore(&snapshot);
+        }
+        assert_eq!(sim.tick(), 200);
+
+        // 倒带后用相同输入重新运行得到相同的结果
+        for _ in 0..100 {
+            sim.step(input);
+        }
+        assert_eq!(sim.score(), score);
+        assert_eq!(sim.player().tile_pos, player);
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ExtraLifeTracker, Fruit, GameRngState, Ghost, GhostCombo, GhostHouse, Level, Lives, MapData,
+    Player,
+};
+
+/// 存档格式版本
+///
+/// 发布后新增的字段要加 `#[serde(default)]`，旧存档缺少的字段使用默认值，版本号不变；
+/// 只有不兼容的改动才增加版本号，读取时拒绝比当前版本新的存档。
+/// 目前只有版本 1，增加版本号时需要在 SaveGame::load 中补上旧版本的迁移
+pub const SAVE_VERSION: u32 = 1;
+
+/// 一局游戏的存档，保存继续游戏所需的全部状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGame {
+    /// 存档格式版本
+    pub version: u32,
+    /// 保存时间（Unix 秒）
+    pub saved_at: u64,
+    /// 地图文件路径，进入下一关和重玩本关时使用
+    pub map_path: PathBuf,
+    /// 随机数种子
+    pub seed: u64,
+    /// 幽灵随机数流的状态
+    pub rng: GameRngState,
+    /// 已经运行的模拟帧数
+    pub tick: u64,
+    /// 当前地图，吃掉的豆子已被清空
+    pub map: MapData,
+    pub player: Player,
+    pub ghosts: Vec<Ghost>,
+    pub fruit: Option<Fruit>,
+    pub score: u32,
+    /// 本局累计吃掉的豆子数
+    pub pellets_eaten: u32,
+    pub lives: Lives,
+    pub level: Level,
+    pub ghost_house: GhostHouse,
+    /// 分散/追捕时间表的当前阶段
+    pub mode_phase: usize,
+    /// 分散/追捕时间表当前阶段已经过的时间
+    pub mode_timer: f32,
+    pub combo: GhostCombo,
+    pub extra_life: ExtraLifeTracker,
+    /// 本局是否用过倒带
+    pub rewind_used: bool,
+}
+
+impl SaveGame {
+    /// 从文件读取存档
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read save {}", path.display()))?;
+        let save: Self = ron::from_str(&text)
+            .with_context(|| format!("Failed to parse save {}", path.display()))?;
+        if save.version > SAVE_VERSION {
+            bail!(
+                "Save {} has version {}, newer than supported version {}",
+                path.display(),
+                save.version,
+                SAVE_VERSION
+            );
+        }
+        Ok(save)
+    }
+
+    /// 保存到文件，目录不存在时自动创建
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, text).with_context(|| format!("Failed to write save {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use glam::IVec2;
+
+    use crate::{MAP_PATH, MapLoader, SaveGame, SimInput, Simulation, TextMapLoader};
+
+    #[test]
+    fn test_save_round_trip() -> anyhow::Result<()> {
+        let map = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut sim = Simulation::new(map, 9);
+        let input = SimInput {
+            direction: Some(IVec2::NEG_X),
+        };
+        for _ in 0..600 {
+            sim.step(input);
+        }
+
+        let text = ron::to_string(&sim.to_save(Path::new(MAP_PATH), 42, true))?;
+        let save: SaveGame = ron::from_str(&text)?;
+        assert_eq!(save.saved_at, 42);
+        assert!(save.rewind_used);
+        let resumed = Simulation::from_save(save)?;
+        assert_eq!(resumed.tick(), sim.tick());
+        assert_eq!(resumed.score(), sim.score());
+        assert_eq!(resumed.map(), sim.map());
+        assert_eq!(resumed.player().tile_pos, sim.player().tile_pos);
+        assert_eq!(
+            resumed.player().movement.direction,
+            sim.player().movement.direction
+        );
+        for (a, b) in resumed.ghosts().iter().zip(sim.ghosts()) {
+            assert_eq!(a.tile_pos, b.tile_pos);
+            assert_eq!(a.mode, b.mode);
+            assert_eq!(a.in_house, b.in_house);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,676 @@
+use std::path::Path;
+
+use anyhow::Context;
+use glam::IVec2;
+
+use crate::{
+    ExtraLifeRule, ExtraLifeTracker, FRUIT_PELLET_THRESHOLDS, Fruit, GameRng, Ghost, GhostCombo,
+    GhostHouse, GhostMode, GhostModeSchedule, GhostType, Level, Lives, MapData, MapLoader,
+    PelletType, Player, SAVE_VERSION, SIMULATION_HZ, SaveGame, SimEvent, SimInput, TextMapLoader,
+    TileType, TryMove, is_colliding, is_power_pellet, is_swapped,
+};
+
+/// 每个模拟帧的时长（秒）
+pub const STEP_SECS: f32 = (1.0 / SIMULATION_HZ) as f32;
+
+/// 一局游戏的完整状态：地图、角色、分数、生命和各种计时器
+///
+/// 不依赖 Bevy，每次调用 step 推进固定的一帧。相同的种子和输入序列总是得到相同的结果。
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// 关卡开始时的地图，进入下一关或重玩本关时使用
+    initial_map: MapData,
+    /// 当前地图，吃掉的豆子会被清空
+    map: MapData,
+    player: Player,
+    /// 幽灵，按 GhostType 排序
+    ghosts: Vec<Ghost>,
+    fruit: Option<Fruit>,
+    score: u32,
+    /// 本局累计吃掉的豆子数
+    pellets_eaten: u32,
+    lives: Lives,
+    level: Level,
+    ghost_house: GhostHouse,
+    schedule: GhostModeSchedule,
+    combo: GhostCombo,
+    extra_life: ExtraLifeTracker,
+    rng: GameRng,
+    /// 已经运行的模拟帧数
+    tick: u64,
+    game_over: bool,
+}
+
+/// 模拟状态的快照，可以随时恢复
+#[derive(Debug, Clone)]
+pub struct SimSnapshot(Box<Simulation>);
+
+impl SimSnapshot {
+    /// 快照所在的模拟帧
+    pub fn tick(&self) -> u64 {
+        self.0.tick
+    }
+}
+
+impl Simulation {
+    /// 从第一关开始新的一局
+    ///
+    /// ## Params
+    /// - map: 地图数据，必须包含玩家出生点
+    /// - seed: 随机数种子
+    pub fn new(map: MapData, seed: u64) -> Self {
+        let (player, ghosts) = spawn_actors(&map);
+        Self {
+            initial_map: map.clone(),
+            map,
+            player,
+            ghosts,
+            fruit: None,
+            score: 0,
+            pellets_eaten: 0,
+            lives: Lives::default(),
+            level: Level::default(),
+            ghost_house: GhostHouse::new(1),
+            schedule: GhostModeSchedule::new(1),
+            combo: GhostCombo::default(),
+            extra_life: ExtraLifeTracker::default(),
+            rng: GameRng::new(seed),
+            tick: 0,
+            game_over: false,
+        }
+    }
+
+    /// 修改奖励生命规则
+    pub fn with_extra_life_rule(mut self, rule: ExtraLifeRule) -> Self {
+        self.set_extra_life_rule(rule);
+        self
+    }
+
+    /// 修改奖励生命规则，新的一局开始前按档案的难度设置
+    pub fn set_extra_life_rule(&mut self, rule: ExtraLifeRule) {
+        self.extra_life = ExtraLifeTracker::new(rule);
+    }
+
+    pub fn map(&self) -> &MapData {
+        &self.map
+    }
+
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+
+    pub fn ghosts(&self) -> &[Ghost] {
+        &self.ghosts
+    }
+
+    pub fn fruit(&self) -> Option<&Fruit> {
+        self.fruit.as_ref()
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn pellets_eaten(&self) -> u32 {
+        self.pellets_eaten
+    }
+
+    pub fn lives(&self) -> &Lives {
+        &self.lives
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    pub fn extra_life_rule(&self) -> ExtraLifeRule {
+        self.extra_life.rule
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    /// 当前这颗能量豆已经吃掉的幽灵数
+    pub fn combo(&self) -> &GhostCombo {
+        &self.combo
+    }
+
+    /// 幽灵是否在追捕玩家：时间表处于追捕阶段，且没有幽灵处于恐惧状态
+    pub fn is_chasing(&self) -> bool {
+        self.schedule.mode() == GhostMode::Chase && !self.any_frightened()
+    }
+
+    /// 保存当前状态
+    pub fn snapshot(&self) -> SimSnapshot {
+        SimSnapshot(Box::new(self.clone()))
+    }
+
+    /// 恢复到快照时的状态
+    pub fn restore(&mut self, snapshot: &SimSnapshot) {
+        *self = (*snapshot.0).clone();
+    }
+
+    /// 生成存档
+    ///
+    /// ## Params
+    /// - map_path: 本局使用的地图文件
+    /// - saved_at: 保存时间（Unix 秒）
+    /// - rewind_used: 本局是否用过倒带
+    pub fn to_save(&self, map_path: &Path, saved_at: u64, rewind_used: bool) -> SaveGame {
+        SaveGame {
+            version: SAVE_VERSION,
+            saved_at,
+            map_path: map_path.to_path_buf(),
+            seed: self.seed(),
+            rng: self.rng.state(),
+            tick: self.tick,
+            map: self.map.clone(),
+            player: self.player.clone(),
+            ghosts: self.ghosts.clone(),
+            fruit: self.fruit.clone(),
+            score: self.score,
+            pellets_eaten: self.pellets_eaten,
+            lives: self.lives.clone(),
+            level: self.level.clone(),
+            ghost_house: self.ghost_house.clone(),
+            mode_phase: self.schedule.phase,
+            mode_timer: self.schedule.timer,
+            combo: self.combo.clone(),
+            extra_life: self.extra_life.clone(),
+            rewind_used,
+        }
+    }
+
+    /// 从存档继续游戏，随机数流从保存时的位置继续
+    pub fn from_save(save: SaveGame) -> anyhow::Result<Self> {
+        let initial_map = TextMapLoader
+            .load_map(&save.map_path)
+            .with_context(|| format!("Failed to load map {}", save.map_path.display()))?;
+        let mut schedule = GhostModeSchedule::new(save.level.value);
+        schedule.phase = save.mode_phase;
+        schedule.timer = save.mode_timer;
+
+        let mut ghosts = save.ghosts;
+        ghosts.sort_by_key(|ghost| ghost.ghost_type as usize);
+        Ok(Self {
+            initial_map,
+            map: save.map,
+            player: save.player,
+            ghosts,
+            fruit: save.fruit,
+            score: save.score,
+            pellets_eaten: save.pellets_eaten,
+            lives: save.lives,
+            level: save.level,
+            ghost_house: save.ghost_house,
+            schedule,
+            combo: save.combo,
+            extra_life: save.extra_life,
+            rng: GameRng::from_state(save.rng),
+            tick: save.tick,
+            game_over: false,
+        })
+    }
+
+    /// 重玩本关：恢复本关开始时的地图和分数
+    pub fn restart_level(&mut self) {
+        self.score = self.level.start_score;
+        self.pellets_eaten -= self.level.pellets_eaten;
+        self.level.reset_progress();
+        self.load_level();
+    }
+
+    /// 推进一个模拟帧
+    ///
+    /// ## Params
+    /// - input: 本帧的玩家输入
+    ///
+    /// ## Return
+    /// 本帧发生的事件，游戏结束后总是为空
+    pub fn step(&mut self, input: SimInput) -> Vec<SimEvent> {
+        let mut events = Vec::new();
+        if self.game_over {
+            return events;
+        }
+        self.tick += 1;
+
+        self.update_player(input, &mut events);
+        self.check_fruit_spawn(&mut events);
+        if self.map.remaining_pellets() == 0 {
+            // 分数保持不变，进入下一关
+            events.push(SimEvent::LevelCleared {
+                level: self.level.value,
+            });
+            self.level.advance(self.score);
+            self.load_level();
+            self.check_extra_life(&mut events);
+            return events;
+        }
+
+        self.update_ghost_house(&mut events);
+        if let Some(mode) = self.schedule.tick(STEP_SECS, self.any_frightened()) {
+            events.push(SimEvent::GhostModeChanged { mode });
+        }
+        self.update_ghosts();
+        self.check_ghost_collisions(&mut events);
+
+        // 所有幽灵都脱离恐惧状态后，连击中断
+        if self.combo.eaten > 0 && !self.any_frightened() {
+            self.combo.reset();
+        }
+
+        self.update_fruit(&mut events);
+        self.check_extra_life(&mut events);
+        events
+    }
+
+    /// 重新加载地图，所有角色回到出生点
+    fn load_level(&mut self) {
+        self.map = self.initial_map.clone();
+        (self.player, self.ghosts) = spawn_actors(&self.map);
+        self.fruit = None;
+        self.ghost_house = GhostHouse::new(self.level.value);
+        self.schedule = GhostModeSchedule::new(self.level.value);
+        self.combo.reset();
+    }
+
+    /// 是否有幽灵处于恐惧状态
+    fn any_frightened(&self) -> bool {
+        self.ghosts
+            .iter()
+            .any(|ghost| ghost.mode == GhostMode::Frightened)
+    }
+
+    /// 按 Blinky、Pinky、Inky、Clyde 的顺序，第一个在基地中的幽灵优先离开
+    fn waiting_ghost(&self) -> Option<GhostType> {
+        self.ghosts
+            .iter()
+            .filter(|ghost| ghost.in_house)
+            .map(|ghost| ghost.ghost_type)
+            .min_by_key(|&ghost_type| ghost_type as usize)
+    }
+
+    /// 处理输入并移动玩家，经过的豆子被吃掉
+    fn update_player(&mut self, input: SimInput, events: &mut Vec<SimEvent>) {
+        if let Some(dir) = input.direction {
+            self.player.intended_direction = dir;
+        }
+        self.player.try_turn(&self.map);
+
+        // 有幽灵处于恐惧状态时玩家加速，下一步要吃豆时玩家减速
+        let frightened = self.any_frightened();
+        let eating = self
+            .player
+            .next_tile(&self.map, self.player.movement.direction)
+            .is_some_and(|pos| self.map.is_pellet(pos.x as usize, pos.y as usize));
+        self.player
+            .movement
+            .set_speed(self.level.spec().player_speed(frightened, eating));
+
+        self.player.prev_tile_pos = self.player.tile_pos;
+        // 快到拐角时提前到达下一格，到达后立即转向
+        if self.player.can_pre_turn(&self.map) {
+            self.player.movement.accumulated_time = self.player.movement.get_move_interval();
+        }
+        // 模拟帧间隔固定且远小于移动间隔，每帧最多走一步
+        if self.player.movement.update(STEP_SECS)
+            && let Some(new_pos) = self.player.try_move(&self.map)
+        {
+            self.player.tile_pos = new_pos;
+            // 到达新格子后检查缓冲的方向
+            self.player.try_turn(&self.map);
+            if self.map.is_pellet(new_pos.x as usize, new_pos.y as usize) {
+                self.map
+                    .set(new_pos.x as usize, new_pos.y as usize, TileType::Empty);
+                self.eat_pellet(new_pos, events);
+            }
+        }
+    }
+
+    /// 吃掉豆子：加分，能量豆让幽灵进入恐惧状态
+    fn eat_pellet(&mut self, position: IVec2, events: &mut Vec<SimEvent>) {
+        let pellet_type = if is_power_pellet(position.x as usize, position.y as usize) {
+            PelletType::Power
+        } else {
+            PelletType::General
+        };
+
+        self.level.pellets_eaten += 1;
+        self.pellets_eaten += 1;
+        match pellet_type {
+            PelletType::General => self.score += 1,
+            PelletType::Power => {
+                self.score += 10;
+                // 新的能量豆重新开始连击
+                self.combo.reset();
+                for ghost in &mut self.ghosts {
+                    ghost.set_frightened(self.level.spec());
+                }
+            }
+        }
+
+        let waiting = self.waiting_ghost();
+        self.ghost_house.on_pellet_eaten(waiting);
+        events.push(SimEvent::PelletEaten {
+            position,
+            pellet_type,
+        });
+    }
+
+    /// 吃掉的豆子数达到阈值时生成奖励水果
+    fn check_fruit_spawn(&mut self, events: &mut Vec<SimEvent>) {
+        let Some(&threshold) = FRUIT_PELLET_THRESHOLDS.get(self.level.fruits_spawned) else {
+            return;
+        };
+        if self.level.pellets_eaten < threshold {
+            return;
+        }
+
+        self.level.fruits_spawned += 1;
+        // 地图中没有标记水果位置时不生成水果
+        if let Some(position) = self.initial_map.fruit_spawn {
+            let fruit = Fruit::new(position, self.level.value);
+            events.push(SimEvent::FruitSpawned {
+                position,
+                fruit_type: fruit.fruit_type,
+            });
+            self.fruit = Some(fruit);
+        }
+    }
+
+    /// 根据豆子计数和空闲计时放出基地中的幽灵
+    fn update_ghost_house(&mut self, events: &mut Vec<SimEvent>) {
+        let Some(waiting) = self.waiting_ghost() else {
+            return;
+        };
+
+        self.ghost_house.tick(STEP_SECS);
+        if self.ghost_house.should_release(waiting) {
+            for ghost in &mut self.ghosts {
+                if ghost.ghost_type == waiting {
+                    ghost.leave_house(&self.map);
+                }
+            }
+            events.push(SimEvent::GhostReleased {
+                ghost_type: waiting,
+            });
+        }
+    }
+
+    /// 幽灵 AI 选择方向并移动
+    fn update_ghosts(&mut self) {
+        // 获取Blinky的位置（用于Inky的计算）
+        let blinky_pos = self
+            .ghosts
+            .iter()
+            .find(|ghost| ghost.ghost_type == GhostType::Blinky)
+            .map(|ghost| ghost.tile_pos)
+            .unwrap_or(self.player.tile_pos);
+        let spec = self.level.spec();
+        let scheduled = self.schedule.mode();
+
+        for ghost in &mut self.ghosts {
+            ghost.update_timers(STEP_SECS, spec, scheduled);
+            ghost.update(
+                self.player.tile_pos,
+                self.player.movement.direction,
+                blinky_pos,
+                &self.map,
+                self.rng.ghost(ghost.ghost_type),
+                spec,
+            );
+
+            ghost.prev_tile_pos = ghost.tile_pos;
+            if ghost.movement.update(STEP_SECS)
+                && let Some(new_pos) = ghost.try_move(&self.map)
+            {
+                ghost.tile_pos = new_pos;
+            }
+        }
+    }
+
+    /// 玩家与幽灵碰撞：恐惧的幽灵被吃掉，否则玩家失去一条命
+    fn check_ghost_collisions(&mut self, events: &mut Vec<SimEvent>) {
+        let player_pos = self.player.position(&self.map, 0.0);
+        let mut caught_by = None;
+
+        for ghost in &mut self.ghosts {
+            let touching = is_colliding(&self.map, player_pos, ghost.position(&self.map, 0.0))
+                || is_swapped(
+                    self.player.prev_tile_pos,
+                    self.player.tile_pos,
+                    ghost.prev_tile_pos,
+                    ghost.tile_pos,
+                );
+            if !touching {
+                continue;
+            }
+
+            match ghost.mode {
+                GhostMode::Frightened => {
+                    ghost.eaten();
+                    let points = self.combo.next_points();
+                    self.score += points;
+                    events.push(SimEvent::GhostEaten {
+                        position: ghost.tile_pos,
+                        ghost_type: ghost.ghost_type,
+                        points,
+                    });
+                }
+                GhostMode::Chase | GhostMode::Scatter => {
+                    // 一次只死亡一次，其余幽灵随重置一起回到出生点
+                    caught_by = Some(ghost.ghost_type);
+                    break;
+                }
+                GhostMode::ReturnToBase => {}
+            }
+        }
+
+        if let Some(ghost_type) = caught_by {
+            self.on_player_caught(ghost_type, events);
+        }
+    }
+
+    /// 玩家被抓住，生命耗尽时游戏结束
+    fn on_player_caught(&mut self, ghost_type: GhostType, events: &mut Vec<SimEvent>) {
+        events.push(SimEvent::PlayerDied {
+            position: self.player.tile_pos,
+            ghost_type,
+        });
+
+        if !self.lives.lose_one() {
+            self.game_over = true;
+            events.push(SimEvent::GameOver);
+            return;
+        }
+
+        // 所有角色回到出生点，改用全局豆子计数放出幽灵，模式时间表从头开始
+        self.ghost_house.on_life_lost();
+        self.schedule.restart();
+        self.player.reset();
+        for ghost in &mut self.ghosts {
+            ghost.reset();
+        }
+    }
+
+    /// 更新水果剩余时间，玩家碰到水果时吃掉
+    fn update_fruit(&mut self, events: &mut Vec<SimEvent>) {
+        let Some(fruit) = self.fruit.as_mut() else {
+            return;
+        };
+
+        if fruit.position == self.player.tile_pos {
+            let points = fruit.fruit_type.points();
+            self.score += points;
+            events.push(SimEvent::FruitEaten {
+                position: fruit.position,
+                fruit_type: fruit.fruit_type,
+                points,
+            });
+            self.fruit = None;
+        } else if fruit.update(STEP_SECS) {
+            events.push(SimEvent::FruitExpired {
+                position: fruit.position,
+            });
+            self.fruit = None;
+        }
+    }
+
+    /// 分数达到奖励条件时增加生命
+    fn check_extra_life(&mut self, events: &mut Vec<SimEvent>) {
+        let new_lives = self.extra_life.check(self.score);
+        if new_lives > 0 {
+            self.lives.value += new_lives;
+            events.push(SimEvent::ExtraLife {
+                lives: self.lives.value,
+                score: self.score,
+            });
+        }
+    }
+}
+
+/// 根据地图上的出生点生成玩家和幽灵
+fn spawn_actors(map: &MapData) -> (Player, Vec<Ghost>) {
+    let mut player = None;
+    let mut ghosts = Vec::new();
+
+    for (y, row) in map.tiles.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            let (x, y) = (x as i32, y as i32);
+            match tile {
+                TileType::Player => player = Some(Player::new(x, y)),
+                TileType::BlinkyGhost => ghosts.push(Ghost::new(x, y, GhostType::Blinky)),
+                TileType::PinkyGhost => ghosts.push(Ghost::new(x, y, GhostType::Pinky)),
+                TileType::InkyGhost => ghosts.push(Ghost::new(x, y, GhostType::Inky)),
+                TileType::ClydeGhost => ghosts.push(Ghost::new(x, y, GhostType::Clyde)),
+                _ => {}
+            }
+        }
+    }
+    ghosts.sort_by_key(|ghost| ghost.ghost_type as usize);
+
+    (player.expect("Map has no player spawn"), ghosts)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{MAP_PATH, MapLoader, POWER_PELLET_POSITIONS, TextMapLoader};
+
+    /// 按固定规律转向的输入序列
+    fn scripted_input(tick: u64) -> SimInput {
+        const DIRS: [IVec2; 4] = [IVec2::NEG_X, IVec2::NEG_Y, IVec2::X, IVec2::Y];
+        SimInput {
+            direction: tick
+                .is_multiple_of(45)
+                .then(|| DIRS[(tick / 45 % 4) as usize]),
+        }
+    }
+
+    fn run(sim: &mut Simulation, ticks: u64) -> Vec<SimEvent> {
+        (0..ticks)
+            .flat_map(|_| sim.step(scripted_input(sim.tick())))
+            .collect()
+    }
+
+    #[test]
+    fn test_deterministic_runs() -> anyhow::Result<()> {
+        let map = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut a = Simulation::new(map.clone(), 7);
+        let mut b = Simulation::new(map, 7);
+
+        let events = run(&mut a, 3000);
+        assert_eq!(events, run(&mut b, 3000));
+        assert!(
+            events
+                .iter()
+                .any(|evt| matches!(evt, SimEvent::PelletEaten { .. }))
+        );
+        assert_eq!(a.score(), b.score());
+        assert_eq!(a.player().tile_pos, b.player().tile_pos);
+        for (ga, gb) in a.ghosts().iter().zip(b.ghosts()) {
+            assert_eq!(ga.tile_pos, gb.tile_pos);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot_restore() -> anyhow::Result<()> {
+        let map = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut sim = Simulation::new(map, 3);
+        run(&mut sim, 600);
+
+        let snapshot = sim.snapshot();
+        assert_eq!(snapshot.tick(), 600);
+        let first = run(&mut sim, 600);
+        let score = sim.score();
+
+        // 从快照重新运行得到完全相同的结果
+        sim.restore(&snapshot);
+        assert_eq!(sim.tick(), 600);
+        assert_eq!(run(&mut sim, 600), first);
+        assert_eq!(sim.score(), score);
+        Ok(())
+    }
+
+    #[test]
+    fn test_frightened_in_house_ends() -> anyhow::Result<()> {
+        let map = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut sim = Simulation::new(map, 5);
+        sim.step(SimInput::default());
+
+        // Clyde 还在基地中时吃到能量豆
+        let (x, y) = POWER_PELLET_POSITIONS[0];
+        sim.eat_pellet(IVec2::new(x as i32, y as i32), &mut Vec::new());
+        let clyde = GhostType::Clyde as usize;
+        assert!(sim.ghosts[clyde].in_house);
+        assert_eq!(sim.ghosts[clyde].mode, GhostMode::Frightened);
+
+        // 恐惧时间结束后所有幽灵恢复，时间表继续推进
+        let ticks = (sim.level.spec().frightened_time / STEP_SECS).ceil() as u64 + 1;
+        for _ in 0..ticks {
+            sim.step(SimInput::default());
+        }
+        assert_ne!(sim.ghosts[clyde].mode, GhostMode::Frightened);
+        assert!(!sim.any_frightened());
+        let timer = sim.schedule.timer;
+        sim.step(SimInput::default());
+        assert!(sim.schedule.timer > timer);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resume_keeps_rng_stream() -> anyhow::Result<()> {
+        let map = TextMapLoader.load_map(Path::new(MAP_PATH))?;
+        let mut sim = Simulation::new(map, 11);
+        run(&mut sim, 1200);
+        // 恐惧的幽灵在路口随机选方向，存档时随机数流已经用过一部分
+        for ghost in &mut sim.ghosts {
+            ghost.set_frightened(sim.level.spec());
+        }
+        run(&mut sim, 300);
+        assert!(sim.rng.state().word_pos.iter().any(|&pos| pos > 0));
+
+        // 存档读回后继续运行，与没有中断的一局完全相同
+        let text = ron::to_string(&sim.to_save(Path::new(MAP_PATH), 0, false))?;
+        let mut resumed = Simulation::from_save(ron::from_str(&text)?)?;
+        assert_eq!(resumed.rng.state(), sim.rng.state());
+        assert_eq!(run(&mut resumed, 1500), run(&mut sim, 1500));
+        assert_eq!(
+            ron::to_string(&resumed.to_save(Path::new(MAP_PATH), 0, false))?,
+            ron::to_string(&sim.to_save(Path::new(MAP_PATH), 0, false))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use glam::IVec2;
+
+use crate::{FruitType, GhostMode, GhostType, PelletType};
+
+/// 一个模拟帧的玩家输入
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SimInput {
+    /// 本帧按下的方向
+    pub direction: Option<IVec2>,
+}
+
+/// 模拟过程中发生的事件，由调用方决定如何展示
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimEvent {
+    /// 吃掉豆子
+    PelletEaten {
+        position: IVec2,
+        pellet_type: PelletType,
+    },
+    /// 出现奖励水果
+    FruitSpawned {
+        position: IVec2,
+        fruit_type: FruitType,
+    },
+    /// 吃掉水果
+    FruitEaten {
+        position: IVec2,
+        fruit_type: FruitType,
+        points: u32,
+    },
+    /// 水果超时消失
+    FruitExpired { position: IVec2 },
+    /// 幽灵离开基地
+    GhostReleased { ghost_type: GhostType },
+    /// 分散/追捕时间表切换模式
+    GhostModeChanged { mode: GhostMode },
+    /// 吃掉幽灵
+    GhostEaten {
+        position: IVec2,
+        ghost_type: GhostType,
+        points: u32,
+    },
+    /// 玩家被幽灵抓住
+    PlayerDied {
+        position: IVec2,
+        ghost_type: GhostType,
+    },
+    /// 奖励生命
+    ExtraLife { lives: u32, score: u32 },
+    /// 完成关卡，已经进入下一关
+    LevelCleared { level: u32 },
+    /// 生命耗尽
+    GameOver,
+}
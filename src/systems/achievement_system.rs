@@ -0,0 +1,101 @@
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    ACHIEVEMENTS_PATH, AchievementDef, AchievementProgress, AchievementUnlockedEvent,
+    AchievementUnlocks, Achievements, ActiveProfile, EatPelletEvent, GameSimulation,
+    LevelClearedEvent, PlayerDeathEvent,
+};
+
+/// 成就读取的游戏消息
+#[derive(SystemParam)]
+pub struct AchievementMessages<'w, 's> {
+    pellet: MessageReader<'w, 's, EatPelletEvent>,
+    death: MessageReader<'w, 's, PlayerDeathEvent>,
+    cleared: MessageReader<'w, 's, LevelClearedEvent>,
+    unlocked: MessageWriter<'w, AchievementUnlockedEvent>,
+}
+
+/// 启动时读取成就定义
+pub fn load_achievements(mut achievements: ResMut<Achievements>) {
+    match AchievementDef::load_all(Path::new(ACHIEVEMENTS_PATH)) {
+        Ok(defs) => achievements.defs = defs,
+        Err(err) => error!("{:#}", err),
+    }
+}
+
+/// 选择档案后读取该档案的解锁记录
+pub fn load_achievement_unlocks(
+    profile: Res<ActiveProfile>,
+    mut achievements: ResMut<Achievements>,
+) {
+    achievements.unlocks = AchievementUnlocks::default();
+    let Some(dir) = profile.dir() else {
+        return;
+    };
+    let path = AchievementUnlocks::path(dir);
+    if !path.exists() {
+        return;
+    }
+    match AchievementUnlocks::load(&path) {
+        Ok(unlocks) => achievements.unlocks = unlocks,
+        Err(err) => error!("{:#}", err),
+    }
+}
+
+/// 新的一局开始时重新跟踪成就进度
+pub fn start_achievement_tracking(sim: Res<GameSimulation>, mut commands: Commands) {
+    commands.insert_resource(AchievementProgress::new(sim.tick() == 0));
+}
+
+/// 每个模拟帧之后更新成就进度，条件满足时解锁并保存
+pub fn track_achievements(
+    mut messages: AchievementMessages,
+    sim: Res<GameSimulation>,
+    time: Res<Time>,
+    mut progress: ResMut<AchievementProgress>,
+    mut achievements: ResMut<Achievements>,
+    profile: Res<ActiveProfile>,
+) {
+    for evt in messages.pellet.read() {
+        progress.record_pellet(evt.pellet_type);
+    }
+    for _ in messages.death.read() {
+        progress.record_death();
+    }
+    for _ in messages.cleared.read() {
+        progress.record_level_cleared();
+    }
+    progress.record_tick(
+        time.delta_secs(),
+        sim.is_chasing(),
+        sim.combo().eaten,
+        sim.score(),
+        sim.level().value,
+    );
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let unlocked = achievements.unlock_met(&progress, now);
+    if unlocked.is_empty() {
+        return;
+    }
+
+    for def in unlocked {
+        info!("Achievement unlocked: {}", def.name);
+        messages.unlocked.write(AchievementUnlockedEvent {
+            name: def.name,
+            description: def.description,
+        });
+    }
+    if let Some(dir) = profile.dir()
+        && let Err(err) = achievements.unlocks.save(&AchievementUnlocks::path(dir))
+    {
+        error!("{:#}", err);
+    }
+}
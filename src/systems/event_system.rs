@@ -1,23 +1,18 @@
 use bevy::prelude::*;
 
-use crate::{EatPelletEvent, Ghost, Pellet, PelletType, PelletUI, Score};
+use crate::{EatPelletEvent, Pellet, PelletUI};
 
-/// 处理吃豆事件
+/// 处理吃豆事件，移除被吃掉的豆子
 pub fn handle_eat_pellet_message(
     mut commands: Commands,
     mut reader: MessageReader<EatPelletEvent>,
     pellet_query: Query<(Entity, &Pellet), With<PelletUI>>,
-    mut score: ResMut<Score>,
-    mut ghost_query: Query<&mut Ghost>,
 ) {
     for evt in reader.read() {
         remove_pellet(&mut commands, &pellet_query, evt.position);
-        update_score(&mut score, &evt.pellet_type);
-        update_ghost(&mut ghost_query, &evt.pellet_type);
 
         // TODO: 播放音效
         // TODO: 触发特效
-        // TODO: 检查是否触发特殊事件（如能量豆）
     }
 }
 
@@ -34,25 +29,3 @@ fn remove_pellet(
         }
     }
 }
-
-/// 更新分数
-fn update_score(score: &mut ResMut<Score>, pellet_type: &PelletType) {
-    match pellet_type {
-        PelletType::General => {
-            score.value += 1; // 普通豆子加1分
-        }
-        PelletType::Power => {
-            score.value += 10; // 能量豆加10分
-            // TODO: 触发幽灵变蓝等特殊效果
-        }
-    }
-}
-
-/// 更新幽灵
-fn update_ghost(ghost_query: &mut Query<&mut Ghost>, pellet_type: &PelletType) {
-    if *pellet_type == PelletType::Power {
-        for mut ghost in ghost_query {
-            ghost.set_frightened(6.0);
-        }
-    }
-}
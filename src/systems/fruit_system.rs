@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use crate::{Fruit, GameSimulation, MapData, spawn_fruit_ui};
+
+/// 按模拟中的水果生成、更新或移除水果实体
+pub fn sync_fruit_ui(
+    mut commands: Commands,
+    sim: Res<GameSimulation>,
+    map_data: Res<MapData>,
+    mut fruit_query: Query<(Entity, &mut Fruit)>,
+) {
+    match sim.fruit() {
+        Some(state) => {
+            if let Some((_, mut fruit)) = fruit_query.iter_mut().next() {
+                fruit.clone_from(state);
+            } else {
+                spawn_fruit_ui(&mut commands, &map_data, state.clone());
+            }
+        }
+        None => {
+            for (entity, _) in &fruit_query {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::{
+    ActiveProfile, BestScore, GameState, HOVERED_COLOR, MenuButton, NONE_COLOR, PRESSED_COLOR,
+    RetryButton, SaveSlotMenu, SaveSlots, Score,
+};
+
+/// 游戏结束时刷新最高分
+pub fn update_best_score(score: Res<Score>, mut best_score: ResMut<BestScore>) {
+    if best_score.update(score.value) {
+        info!("New best score: {}", score.value);
+    }
+}
+
+/// 处理游戏结束页面的按钮
+#[allow(clippy::type_complexity)]
+pub fn handle_game_over_button(
+    mut interaction_query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            Option<&RetryButton>,
+            Option<&MenuButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut next_state: ResMut<NextState<GameState>>,
+    profile: Res<ActiveProfile>,
+    mut slots: ResMut<SaveSlots>,
+    mut slot_menu: ResMut<SaveSlotMenu>,
+) {
+    for (interaction, mut color, retry_btn, menu_btn) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = BackgroundColor(PRESSED_COLOR);
+
+                if retry_btn.is_some() {
+                    // 看完回放后重来是新的一局，和 Start 一样需要存档槽位；
+                    // 槽位都满时回到主菜单确认覆盖
+                    if slots.active.is_none() {
+                        let Some(slot) = slot_menu.new_game_slot(&slots) else {
+                            info!("Retry pressed with all save slots full -> Switching to Menu");
+                            next_state.set(GameState::Menu);
+                            continue;
+                        };
+                        slots.active = Some(slot);
+                    }
+                    info!("Retry pressed -> Switching to Playing");
+                    next_state.set(GameState::Playing);
+                }
+
+                if menu_btn.is_some() {
+                    // 命令行回放在选择档案之前开始，看完后先选择档案再进入主菜单
+                    if profile.0.is_some() {
+                        info!("Main menu pressed -> Switching to Menu");
+                        next_state.set(GameState::Menu);
+                    } else {
+                        info!("Main menu pressed without a profile -> Switching to Profiles");
+                        next_state.set(GameState::Profiles);
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(HOVERED_COLOR);
+            }
+            Interaction::None => {
+                *color = BackgroundColor(NONE_COLOR);
+            }
+        }
+    }
+}
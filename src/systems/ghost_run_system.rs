@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use crate::{
+    ActiveProfile, GameSimulation, MAP_PATH, PERSONAL_BEST_PREFIX, REPLAY_DIR, REPLAY_EXTENSION,
+    Replay, ReplayPlayback, SimSnapshot, Simulation,
+};
+
+/// 当前档案在当前地图上分数最高的一局
+#[derive(Resource, Default)]
+pub struct PersonalBest {
+    /// 个人最佳的回放，还没有玩过时为 None
+    pub replay: Option<Replay>,
+    /// 个人最佳的分数
+    pub score: u32,
+}
+
+impl PersonalBest {
+    /// 本局分数超过个人最佳时保存本局回放
+    ///
+    /// ## Params
+    /// - profile_dir: 当前档案的目录，为 None 时只更新内存中的记录
+    ///
+    /// ## Return
+    /// 若创造了新纪录则返回 true
+    pub fn update(&mut self, profile_dir: Option<&Path>, replay: &Replay, score: u32) -> bool {
+        if score <= self.score {
+            return false;
+        }
+
+        if let Some(dir) = profile_dir
+            && let Err(err) = replay.save(&personal_best_path(dir, &replay.map_path))
+        {
+            error!("{:#}", err);
+        }
+        self.replay = Some(replay.clone());
+        self.score = score;
+        true
+    }
+}
+
+/// 与当前这一局同步运行的个人最佳回放
+///
+/// 回放在独立的模拟中逐帧推进，玩家的位置、吃豆数和分数都来自这个模拟
+#[derive(Resource)]
+pub struct GhostRun {
+    replay: Replay,
+    sim: Simulation,
+}
+
+impl GhostRun {
+    /// 个人最佳回放中的模拟
+    pub fn sim(&self) -> &Simulation {
+        &self.sim
+    }
+
+    /// 个人最佳回放是否已经结束
+    pub fn is_finished(&self) -> bool {
+        self.replay.is_finished(&self.sim)
+    }
+
+    /// 保存回放进度，与当前这一局一起倒带
+    pub fn snapshot(&self) -> SimSnapshot {
+        self.sim.snapshot()
+    }
+
+    /// 恢复回放进度
+    pub fn restore(&mut self, snapshot: &SimSnapshot) {
+        self.sim.restore(snapshot);
+    }
+}
+
+/// 是否显示个人最佳回放，按 G 切换
+#[derive(Resource)]
+pub struct GhostRunSettings {
+    pub enabled: bool,
+}
+
+impl Default for GhostRunSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// 档案在指定地图上的个人最佳回放文件
+pub fn personal_best_path(profile_dir: &Path, map_path: &Path) -> PathBuf {
+    let map_name = map_path
+        .file_stem()
+        .map_or_else(|| "map".into(), |stem| stem.to_string_lossy());
+    profile_dir.join(REPLAY_DIR).join(format!(
+        "{}{}.{}",
+        PERSONAL_BEST_PREFIX, map_name, REPLAY_EXTENSION
+    ))
+}
+
+/// 选择档案后读取该档案在当前地图上的个人最佳回放，运行一遍得到最终分数
+pub fn load_personal_best(profile: Res<ActiveProfile>, mut best: ResMut<PersonalBest>) {
+    *best = PersonalBest::default();
+    let Some(dir) = profile.dir() else {
+        return;
+    };
+    let path = personal_best_path(dir, Path::new(MAP_PATH));
+    if !path.exists() {
+        return;
+    }
+
+    match Replay::load(&path).and_then(|replay| Ok((replay.run()?.score(), replay))) {
+        Ok((score, replay)) => {
+            info!("Personal best on {}: {}", MAP_PATH, score);
+            best.replay = Some(replay);
+            best.score = score;
+        }
+        Err(err) => error!("Failed to load personal best {}: {:#}", path.display(), err),
+    }
+}
+
+/// 新的一局开始时从头播放个人最佳回放，观看回放和从存档继续时不显示
+pub fn start_ghost_run(
+    mut commands: Commands,
+    sim: Res<GameSimulation>,
+    best: Res<PersonalBest>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    let replay = best
+        .replay
+        .as_ref()
+        .filter(|_| playback.is_none() && sim.tick() == 0);
+    match replay.map(|replay| (replay, replay.simulation())) {
+        Some((replay, Ok(sim))) => commands.insert_resource(GhostRun {
+            replay: replay.clone(),
+            sim,
+        }),
+        Some((_, Err(err))) => {
+            error!("{:#}", err);
+            commands.remove_resource::<GhostRun>();
+        }
+        None => commands.remove_resource::<GhostRun>(),
+    }
+}
+
+/// 个人最佳回放跟随当前这一局推进一个模拟帧
+pub fn step_ghost_run(mut ghost_run: ResMut<GhostRun>) {
+    let GhostRun { replay, sim } = &mut *ghost_run;
+    if !replay.is_finished(sim) {
+        replay.step(sim);
+    }
+}
+
+/// G 键切换个人最佳回放的显示
+pub fn toggle_ghost_run(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<GhostRunSettings>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyG) {
+        settings.enabled = !settings.enabled;
+        info!("Ghost run visible: {}", settings.enabled);
+    }
+}
+
+/// 当前这一局相对个人最佳的领先量：吃豆数和分数
+///
+/// ## Return
+/// 正数表示领先，负数表示落后
+pub fn ghost_run_delta(sim: &GameSimulation, ghost_run: &GhostRun) -> (i64, i64) {
+    let pellets = i64::from(sim.pellets_eaten()) - i64::from(ghost_run.sim.pellets_eaten());
+    let score = i64::from(sim.score()) - i64::from(ghost_run.sim.score());
+    (pellets, score)
+}
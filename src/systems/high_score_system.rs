@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use bevy::{
+    input::{ButtonState, keyboard::KeyboardInput},
+    prelude::*,
+};
+
+use crate::{
+    ActiveProfile, BestScore, HighScoreTable, HighScores, INITIALS_LEN, InitialsEntry, Level,
+    MAP_PATH, Score,
+};
+
+/// 选择档案后读取该档案在当前地图上的排行榜，最高分从榜首开始
+pub fn load_high_scores(
+    profile: Res<ActiveProfile>,
+    mut high_scores: ResMut<HighScores>,
+    mut best_score: ResMut<BestScore>,
+) {
+    high_scores.table = HighScoreTable::default();
+    if let Some(dir) = profile.dir() {
+        let path = HighScoreTable::path(dir, Path::new(MAP_PATH));
+        if path.exists() {
+            match HighScoreTable::load(&path) {
+                Ok(table) => high_scores.table = table,
+                Err(err) => error!("{:#}", err),
+            }
+        }
+    }
+    best_score.value = high_scores.table.best();
+}
+
+/// 游戏结束时分数能上榜则开始输入缩写
+pub fn start_initials_entry(
+    mut commands: Commands,
+    score: Res<Score>,
+    level: Res<Level>,
+    high_scores: Res<HighScores>,
+) {
+    if high_scores.table.qualifies(score.value) {
+        commands.insert_resource(InitialsEntry::new(score.value, level.value));
+    }
+}
+
+/// 输入缩写：上下键切换字母，左右键移动光标，也可以直接打字，回车确认
+pub fn handle_initials_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut key_reader: MessageReader<KeyboardInput>,
+    mut entry: ResMut<InitialsEntry>,
+    mut high_scores: ResMut<HighScores>,
+    profile: Res<ActiveProfile>,
+) {
+    if entry.rank.is_some() {
+        key_reader.clear();
+        return;
+    }
+
+    for input in key_reader.read() {
+        if input.state != ButtonState::Pressed {
+            continue;
+        }
+        if let Some(letter) = input
+            .text
+            .as_ref()
+            .and_then(|text| text.bytes().next())
+            .filter(u8::is_ascii_alphabetic)
+        {
+            entry.type_letter(letter);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        entry.cycle(1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        entry.cycle(-1);
+    }
+    if keyboard.any_just_pressed([KeyCode::ArrowLeft, KeyCode::Backspace]) {
+        entry.move_cursor(-1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        entry.move_cursor(1);
+    }
+    if keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        // 回车先逐个确认字母，最后一个字母确认后上榜
+        if entry.cursor + 1 < INITIALS_LEN {
+            entry.move_cursor(1);
+        } else {
+            submit_high_score(&mut high_scores, &mut entry, profile.dir());
+        }
+    }
+}
+
+/// 离开游戏结束页面时，还没确认的缩写按当前字母上榜
+pub fn finish_initials_entry(
+    mut commands: Commands,
+    entry: Option<ResMut<InitialsEntry>>,
+    mut high_scores: ResMut<HighScores>,
+    profile: Res<ActiveProfile>,
+) {
+    let Some(mut entry) = entry else {
+        return;
+    };
+    if entry.rank.is_none() {
+        submit_high_score(&mut high_scores, &mut entry, profile.dir());
+    }
+    commands.remove_resource::<InitialsEntry>();
+}
+
+/// 把本局写入排行榜并保存到档案目录
+fn submit_high_score(
+    high_scores: &mut HighScores,
+    entry: &mut InitialsEntry,
+    profile_dir: Option<&Path>,
+) {
+    let Some(rank) = high_scores.table.insert(entry.entry()) else {
+        return;
+    };
+    entry.rank = Some(rank);
+    info!(
+        "High score #{}: {} {}",
+        rank + 1,
+        entry.initials(),
+        entry.score
+    );
+
+    let Some(dir) = profile_dir else {
+        return;
+    };
+    let path = HighScoreTable::path(dir, Path::new(MAP_PATH));
+    if let Err(err) = high_scores.table.save(&path) {
+        error!("{:#}", err);
+    }
+}
@@ -0,0 +1,91 @@
+use bevy::{
+    ecs::{schedule::ScheduleConfigs, system::ScheduleSystem},
+    prelude::*,
+};
+
+use crate::{
+    Fruit, GameSimulation, Ghost, GhostRunUI, Level, LevelClearedEvent, Lives, MapData, MapUI,
+    Player, ReplayPlayback, Score, ScoreHud, ScorePopup, Simulation, default_seed, read_map_data,
+    setup_map_ui, spawn_map, start_achievement_tracking, start_ghost_run, start_recording,
+    start_stats_tracking,
+};
+
+/// 属于一局关卡的实体：地图、玩家、幽灵、水果、得分提示、个人最佳回放和分数 HUD
+pub type LevelEntityFilter = Or<(
+    With<MapUI>,
+    With<Player>,
+    With<Ghost>,
+    With<Fruit>,
+    With<ScorePopup>,
+    With<GhostRunUI>,
+    With<ScoreHud>,
+)>;
+
+/// 进入一局游戏时运行的系统：生成地图，开始录制回放、个人最佳回放、统计和成就
+///
+/// 从主菜单、结束画面和档案页面（命令行回放）进入游戏时都要运行，从暂停恢复时不运行
+pub fn start_game_systems() -> ScheduleConfigs<ScheduleSystem> {
+    (
+        setup_map_ui,
+        start_recording,
+        start_ghost_run,
+        start_stats_tracking,
+        start_achievement_tracking,
+    )
+        .into_configs()
+}
+
+/// 关卡完成后按模拟中下一关的地图重新生成实体
+pub fn handle_level_cleared_message(
+    mut commands: Commands,
+    mut reader: MessageReader<LevelClearedEvent>,
+    map_data: Res<MapData>,
+    entity_query: Query<Entity, LevelEntityFilter>,
+) {
+    if reader.read().count() > 0 {
+        reload_level(&mut commands, &map_data, &entity_query);
+    }
+}
+
+/// 清理本关的实体，按地图数据重新生成实体
+pub fn reload_level(
+    commands: &mut Commands,
+    map_data: &MapData,
+    entity_query: &Query<Entity, LevelEntityFilter>,
+) {
+    despawn_level(commands, entity_query);
+    spawn_map(commands, map_data);
+}
+
+/// 清理本关的地图、玩家和幽灵实体
+pub fn despawn_level(commands: &mut Commands, entity_query: &Query<Entity, LevelEntityFilter>) {
+    for entity in entity_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 放弃当前这一局，从第一关重新开始
+///
+/// 每一局使用新的随机种子，命令行或环境变量固定了种子时每一局都用这个种子，相同输入下幽灵行为可以复现
+///
+/// 正在播放的回放也随之结束
+pub fn reset_game(
+    mut commands: Commands,
+    mut sim: ResMut<GameSimulation>,
+    mut map_data: ResMut<MapData>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    mut level: ResMut<Level>,
+    entity_query: Query<Entity, LevelEntityFilter>,
+) {
+    despawn_level(&mut commands, &entity_query);
+    commands.remove_resource::<ReplayPlayback>();
+    let seed = default_seed();
+    info!("Game RNG seed: {}", seed);
+    **sim = Simulation::new(read_map_data(), seed);
+
+    *map_data = sim.map().clone();
+    score.value = sim.score();
+    lives.clone_from(sim.lives());
+    level.clone_from(sim.level());
+}
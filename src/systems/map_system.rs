@@ -1,14 +1,17 @@
 use std::path::Path;
 
-use bevy::ecs::system::Commands;
+use bevy::ecs::system::{Commands, Res};
 
-use crate::{MAP_PATH, MapLoader, TextMapLoader};
+use crate::{GameSimulation, MAP_PATH, MapData, MapLoader, TextMapLoader};
 
-/// 加载地图数据，全局只加载一次
-pub fn load_map_data(mut commands: Commands) {
+/// 把模拟中的地图作为渲染用的地图数据
+pub fn load_map_data(mut commands: Commands, sim: Res<GameSimulation>) {
+    commands.insert_resource(sim.map().clone());
+}
+
+/// 从磁盘读取地图数据
+pub fn read_map_data() -> MapData {
     let loader = TextMapLoader;
     let map_path = Path::new(MAP_PATH);
-    let map_data = loader.load_map(map_path).expect("Failed to load map");
-
-    commands.insert_resource(map_data);
+    loader.load_map(map_path).expect("Failed to load map")
 }
@@ -1,30 +1,83 @@
 use bevy::prelude::*;
 
-use crate::{GameState, HOVERED_COLOR, NONE_COLOR, PRESSED_COLOR, QuitButton, StartButton};
+use crate::{
+    ActiveProfile, ContinueButton, DeleteSaveButton, GameSimulation, GameState, HOVERED_COLOR,
+    HighScoresButton, MenuButton, NONE_COLOR, PRESSED_COLOR, QuitButton, Replay, ReplayButton,
+    Rewind, SaveSlotMenu, SaveSlots, SlotConfirm, StartButton, StatisticsButton, resume_game,
+    start_playback,
+};
 
 /// 处理菜单页面的按钮
+#[allow(clippy::type_complexity)]
 pub fn handle_menu_button(
+    mut commands: Commands,
     mut interaction_query: Query<
         (
             &Interaction,
             &mut BackgroundColor,
-            Option<&StartButton>,
+            Option<&ContinueButton>,
+            Option<&ReplayButton>,
+            Option<&HighScoresButton>,
+            Option<&StatisticsButton>,
             Option<&QuitButton>,
         ),
         (Changed<Interaction>, With<Button>),
     >,
     mut next_state: ResMut<NextState<GameState>>,
+    mut sim: ResMut<GameSimulation>,
+    mut slots: ResMut<SaveSlots>,
+    mut rewind: ResMut<Rewind>,
     mut exit: MessageWriter<AppExit>,
 ) {
-    for (interaction, mut color, start_btn, quit_btn) in &mut interaction_query {
+    for (
+        interaction,
+        mut color,
+        continue_btn,
+        replay_btn,
+        high_scores_btn,
+        statistics_btn,
+        quit_btn,
+    ) in &mut interaction_query
+    {
         match *interaction {
             Interaction::Pressed => {
                 // 按下时的视觉反馈
                 *color = BackgroundColor(PRESSED_COLOR);
 
-                if start_btn.is_some() {
-                    info!("Start pressed -> Switching to Playing");
-                    next_state.set(GameState::Playing);
+                if let Some(&ContinueButton(slot)) = continue_btn {
+                    match resume_game(&mut commands, &mut sim, &mut rewind, &mut slots, slot) {
+                        Ok(()) => {
+                            info!("Continue pressed -> Resuming save slot {}", slot + 1);
+                            next_state.set(GameState::Playing);
+                        }
+                        Err(err) => error!("Failed to resume save slot {}: {:#}", slot + 1, err),
+                    }
+                }
+
+                if let Some(ReplayButton(path)) = replay_btn {
+                    // 播放最近一局的回放
+                    match Replay::load(path)
+                        .and_then(|replay| start_playback(&mut commands, &mut sim, replay))
+                    {
+                        Ok(()) => {
+                            // 观看回放不占用存档槽位
+                            slots.active = None;
+                            rewind.reset();
+                            info!("Watch replay pressed -> Playing {}", path.display());
+                            next_state.set(GameState::Playing);
+                        }
+                        Err(err) => error!("Failed to play replay {}: {:#}", path.display(), err),
+                    }
+                }
+
+                if high_scores_btn.is_some() {
+                    info!("High scores pressed -> Switching to HighScores");
+                    next_state.set(GameState::HighScores);
+                }
+
+                if statistics_btn.is_some() {
+                    info!("Statistics pressed -> Switching to Statistics");
+                    next_state.set(GameState::Statistics);
                 }
 
                 if quit_btn.is_some() {
@@ -43,3 +96,93 @@ pub fn handle_menu_button(
         }
     }
 }
+
+/// 处理主菜单中的存档槽位按钮：开始新的一局和删除存档
+#[allow(clippy::type_complexity)]
+pub fn handle_save_slot_button(
+    mut interaction_query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            Option<&StartButton>,
+            Option<&DeleteSaveButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut slots: ResMut<SaveSlots>,
+    mut slot_menu: ResMut<SaveSlotMenu>,
+    mut rewind: ResMut<Rewind>,
+    profile: Res<ActiveProfile>,
+) {
+    for (interaction, mut color, start_btn, delete_btn) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = BackgroundColor(PRESSED_COLOR);
+
+                if start_btn.is_some() {
+                    // 新的一局占用一个存档槽位，中途退出时保存到这里；
+                    // 槽位都满时要再点一次确认覆盖最早的存档
+                    let Some(slot) = slot_menu.new_game_slot(&slots) else {
+                        continue;
+                    };
+                    slots.active = Some(slot);
+                    rewind.reset();
+                    info!(
+                        "Start pressed -> Switching to Playing (save slot {})",
+                        slot + 1
+                    );
+                    next_state.set(GameState::Playing);
+                }
+
+                if let Some(&DeleteSaveButton(slot)) = delete_btn {
+                    // 删除的存档无法恢复，需要再点一次确认
+                    if slot_menu.confirm != Some(SlotConfirm::Delete(slot)) {
+                        slot_menu.confirm = Some(SlotConfirm::Delete(slot));
+                        continue;
+                    }
+                    if let Some(dir) = profile.dir() {
+                        slots.delete(dir, slot);
+                        info!("Save slot {} deleted", slot + 1);
+                    }
+                    slot_menu.confirm = None;
+                }
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(HOVERED_COLOR);
+            }
+            Interaction::None => {
+                *color = BackgroundColor(NONE_COLOR);
+            }
+        }
+    }
+}
+
+/// 处理排行榜和统计页面的返回按钮
+#[allow(clippy::type_complexity)]
+pub fn handle_back_button(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, Option<&MenuButton>),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color, menu_btn) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = BackgroundColor(PRESSED_COLOR);
+
+                if menu_btn.is_some() {
+                    info!("Back pressed -> Switching to Menu");
+                    next_state.set(GameState::Menu);
+                }
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(HOVERED_COLOR);
+            }
+            Interaction::None => {
+                *color = BackgroundColor(NONE_COLOR);
+            }
+        }
+    }
+}
@@ -1,17 +1,40 @@
+mod achievement_system;
 mod event_system;
+mod fruit_system;
+mod ghost_run_system;
+mod high_score_system;
+mod game_over_system;
 pub(crate) mod fonts;
+mod level_system;
 mod map_system;
 mod menu_system;
+mod pause_system;
 mod pellet_system;
 mod player_system;
+mod profile_system;
+mod replay_system;
+mod rewind_system;
+mod save_system;
 pub(crate) mod ui;
-mod ghost_system;
+mod simulation_system;
+mod stats_system;
 
+pub use achievement_system::*;
 pub use event_system::*;
-pub use fonts::{FontAssets, load_font_assets};
+pub use fruit_system::*;
+pub use ghost_run_system::*;
+pub use high_score_system::*;
+pub use game_over_system::*;
+pub use fonts::{FontAssets, LocalizedText, load_font_assets, update_chinese_text_fonts};
+pub use level_system::*;
 pub use map_system::*;
 pub use menu_system::*;
-pub use pellet_system::*;
+pub use pause_system::*;
 pub use player_system::*;
+pub use profile_system::*;
+pub use replay_system::*;
+pub use rewind_system::*;
+pub use save_system::*;
 pub use ui::*;
-pub use ghost_system::*;
\ No newline at end of file
+pub use simulation_system::*;
+pub use stats_system::*;
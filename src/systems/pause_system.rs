@@ -0,0 +1,100 @@
+use bevy::{prelude::*, window::WindowFocused};
+
+use crate::{
+    GameSimulation, GameState, HOVERED_COLOR, LevelEntityFilter, MapData, MenuButton, NONE_COLOR,
+    PRESSED_COLOR, ReplayPlayback, ReplayRecorder, RestartButton, ResumeButton, reload_level,
+};
+
+/// Esc / P 切换暂停
+pub fn toggle_pause(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !keyboard.any_just_pressed([KeyCode::Escape, KeyCode::KeyP]) {
+        return;
+    }
+
+    match state.get() {
+        GameState::Playing => next_state.set(GameState::Paused),
+        GameState::Paused => next_state.set(GameState::Playing),
+        _ => {}
+    }
+}
+
+/// 窗口失去焦点时自动暂停
+pub fn pause_on_focus_lost(
+    mut reader: MessageReader<WindowFocused>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if reader.read().any(|evt| !evt.focused) {
+        info!("Window lost focus -> Switching to Paused");
+        next_state.set(GameState::Paused);
+    }
+}
+
+/// 暂停虚拟时间，冻结所有读取 Time 的移动和计时器
+pub fn pause_game_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+/// 恢复虚拟时间
+pub fn resume_game_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+/// 处理暂停菜单的按钮
+#[allow(clippy::type_complexity)]
+pub fn handle_pause_button(
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            Option<&ResumeButton>,
+            Option<&RestartButton>,
+            Option<&MenuButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut sim: ResMut<GameSimulation>,
+    mut map_data: ResMut<MapData>,
+    mut recorder: ResMut<ReplayRecorder>,
+    entity_query: Query<Entity, LevelEntityFilter>,
+) {
+    for (interaction, mut color, resume_btn, restart_btn, menu_btn) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = BackgroundColor(PRESSED_COLOR);
+
+                if resume_btn.is_some() {
+                    info!("Resume pressed -> Switching to Playing");
+                    next_state.set(GameState::Playing);
+                }
+
+                if restart_btn.is_some() {
+                    info!("Restart pressed -> Restarting level {}", sim.level().value);
+                    // 恢复本关开始时的地图和分数，播放回放时改由玩家接手
+                    recorder.record_restart(sim.tick());
+                    commands.remove_resource::<ReplayPlayback>();
+                    sim.restart_level();
+                    *map_data = sim.map().clone();
+                    reload_level(&mut commands, &map_data, &entity_query);
+                    next_state.set(GameState::Playing);
+                }
+
+                if menu_btn.is_some() {
+                    info!("Main menu pressed -> Switching to Menu");
+                    next_state.set(GameState::Menu);
+                }
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(HOVERED_COLOR);
+            }
+            Interaction::None => {
+                *color = BackgroundColor(NONE_COLOR);
+            }
+        }
+    }
+}
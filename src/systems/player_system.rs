@@ -1,62 +1,34 @@
-use std::usize;
-
-use bevy::prelude::*;
-
-use crate::{
-    MapData, Player, TryMove, check_position,
-    components::{EatPelletEvent, TileType},
+use bevy::{
+    input::{ButtonState, keyboard::KeyboardInput},
+    prelude::*,
 };
 
-/// 处理玩家输入，设置玩家方向
-pub fn handle_player_input(
-    mut query: Query<&mut Player>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    map_data: Res<MapData>,
+use crate::{ActiveProfile, KeyBindings, PlayerInput};
+
+/// 读取键盘输入，记录最近按下的方向
+///
+/// 键盘状态每个渲染帧更新一次，模拟帧可能一帧运行多次或一次不运行，所以先存到 PlayerInput 中
+pub fn read_player_input(
+    mut key_reader: MessageReader<KeyboardInput>,
+    profile: Res<ActiveProfile>,
+    mut input: ResMut<PlayerInput>,
 ) {
-    for mut player in &mut query {
-        let mut dir = IVec2::ZERO;
-        if keyboard.pressed(KeyCode::KeyW) {
-            dir.y -= 1;
-        }
-        if keyboard.pressed(KeyCode::KeyS) {
-            dir.y += 1;
-        }
-        if keyboard.pressed(KeyCode::KeyA) {
-            dir.x -= 1;
-        }
-        if keyboard.pressed(KeyCode::KeyD) {
-            dir.x += 1;
-        }
+    // 按键来自当前档案的设置，没有选中档案时使用默认按键
+    let keys = profile
+        .0
+        .as_ref()
+        .map_or_else(KeyBindings::default, |profile| {
+            profile.settings.key_bindings
+        });
 
-        // 检查目标位置是否可移动
-        if dir != IVec2::ZERO {
-            let next_pos = player.tile_pos + dir;
-            if check_position(next_pos.x, next_pos.y, map_data.width, map_data.height)
-                && !map_data.is_wall(next_pos.x as usize, next_pos.y as usize)
-            {
-                player.movement.set_direction(dir);
-            }
-        }
-    }
-}
+    // 按键消息按到达顺序读取，同一帧按下多个方向键时以最后按下的为准，不会出现斜向
+    let pressed = key_reader
+        .read()
+        .filter(|key| key.state == ButtonState::Pressed && !key.repeat)
+        .filter_map(|key| keys.direction(key.key_code))
+        .last();
 
-/// 更新玩家
-pub fn player_update(
-    mut query: Query<&mut Player>,
-    mut map_data: ResMut<MapData>,
-    mut eat_evt: MessageWriter<EatPelletEvent>,
-    time: Res<Time>,
-) {
-    for mut player in &mut query {
-        while player.movement.update(time.delta_secs()) {
-            // 执行移动
-            if let Some(new_pos) = player.try_move(&map_data) {
-                player.tile_pos = new_pos;
-                if map_data.is_pellet(new_pos.x as usize, new_pos.y as usize) {
-                    map_data.set(new_pos.x as usize, new_pos.y as usize, TileType::Empty);
-                    eat_evt.write(EatPelletEvent::new(new_pos));
-                }
-            }
-        }
+    if pressed.is_some() {
+        input.direction = pressed;
     }
 }
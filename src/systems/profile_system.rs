@@ -0,0 +1,264 @@
+use bevy::{
+    input::{ButtonState, keyboard::KeyboardInput},
+    prelude::*,
+};
+
+use crate::{
+    ActiveProfile, DeleteProfileButton, GameSimulation, GameState, HOVERED_COLOR, NONE_COLOR,
+    NewProfileButton, PRESSED_COLOR, PROFILE_NAME_MAX_LEN, ProfileSetting, ProfileSettingButton,
+    ProfileStore, RenameProfileButton, SelectProfileButton, localization::LanguageSettings,
+};
+
+/// 正在输入的档案名
+#[derive(Debug, Clone, Default)]
+pub struct ProfileNameEntry {
+    pub name: String,
+    /// 重命名的档案，新建档案时为 None
+    pub renaming: Option<String>,
+}
+
+/// 档案页面的状态，变化时重新生成页面
+#[derive(Resource, Default)]
+pub struct ProfileMenu {
+    pub store: ProfileStore,
+    /// 所有档案名
+    pub profiles: Vec<String>,
+    pub entry: Option<ProfileNameEntry>,
+    /// 等待再次点击确认删除的档案
+    pub confirm_delete: Option<String>,
+    /// 上一次操作失败的原因
+    pub error: Option<String>,
+}
+
+impl ProfileMenu {
+    /// 重新读取档案列表
+    fn refresh(&mut self) {
+        self.profiles = self.store.list();
+        self.entry = None;
+        self.confirm_delete = None;
+    }
+}
+
+/// 进入档案页面时读取档案列表
+pub fn refresh_profile_menu(mut menu: ResMut<ProfileMenu>) {
+    menu.refresh();
+    menu.error = None;
+}
+
+/// 处理档案页面的按钮：选择、新建、重命名和删除档案
+#[allow(clippy::type_complexity)]
+pub fn handle_profiles_button(
+    mut interaction_query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            Option<&SelectProfileButton>,
+            Option<&NewProfileButton>,
+            Option<&RenameProfileButton>,
+            Option<&DeleteProfileButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut menu: ResMut<ProfileMenu>,
+    mut active: ResMut<ActiveProfile>,
+    mut language_settings: ResMut<LanguageSettings>,
+    mut sim: ResMut<GameSimulation>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color, select_btn, new_btn, rename_btn, delete_btn) in
+        &mut interaction_query
+    {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = BackgroundColor(PRESSED_COLOR);
+
+                if let Some(SelectProfileButton(name)) = select_btn {
+                    match menu.store.open(name) {
+                        Ok(profile) => {
+                            info!("Profile {:?} selected -> Switching to Menu", name);
+                            // 档案的设置立即生效，新的一局按档案的难度开始
+                            language_settings.set_language(profile.settings.language);
+                            sim.set_extra_life_rule(profile.settings.difficulty.extra_life_rule());
+                            active.0 = Some(profile);
+                            next_state.set(GameState::Menu);
+                        }
+                        Err(err) => menu.error = Some(format!("{:#}", err)),
+                    }
+                }
+
+                if new_btn.is_some() {
+                    menu.confirm_delete = None;
+                    menu.entry = Some(ProfileNameEntry::default());
+                }
+
+                if let Some(RenameProfileButton(name)) = rename_btn {
+                    menu.confirm_delete = None;
+                    menu.entry = Some(ProfileNameEntry {
+                        name: name.clone(),
+                        renaming: Some(name.clone()),
+                    });
+                }
+
+                if let Some(DeleteProfileButton(name)) = delete_btn {
+                    // 删除会丢失档案的所有数据，需要再点一次确认
+                    if menu.confirm_delete.as_ref() != Some(name) {
+                        menu.confirm_delete = Some(name.clone());
+                        continue;
+                    }
+                    match menu.store.delete(name) {
+                        Ok(()) => {
+                            info!("Profile {:?} deleted", name);
+                            if active
+                                .0
+                                .as_ref()
+                                .is_some_and(|profile| &profile.name == name)
+                            {
+                                active.0 = None;
+                            }
+                            menu.refresh();
+                        }
+                        Err(err) => menu.error = Some(format!("{:#}", err)),
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(HOVERED_COLOR);
+            }
+            Interaction::None => {
+                *color = BackgroundColor(NONE_COLOR);
+            }
+        }
+    }
+}
+
+/// 输入档案名：直接打字，退格删除，回车确认，Esc 取消
+pub fn handle_profile_name_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut key_reader: MessageReader<KeyboardInput>,
+    mut menu: ResMut<ProfileMenu>,
+    mut active: ResMut<ActiveProfile>,
+) {
+    if menu.entry.is_none() {
+        key_reader.clear();
+        return;
+    }
+
+    for input in key_reader.read() {
+        if input.state != ButtonState::Pressed {
+            continue;
+        }
+        let Some(text) = &input.text else {
+            continue;
+        };
+        let Some(entry) = menu.entry.as_mut() else {
+            return;
+        };
+        for c in text.chars() {
+            if (c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+                && entry.name.chars().count() < PROFILE_NAME_MAX_LEN
+            {
+                entry.name.push(c);
+            }
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Backspace)
+        && let Some(entry) = menu.entry.as_mut()
+    {
+        entry.name.pop();
+    }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        menu.entry = None;
+        return;
+    }
+    if !keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        return;
+    }
+
+    let Some(entry) = menu.entry.clone() else {
+        return;
+    };
+    let result = match &entry.renaming {
+        Some(old_name) => menu.store.rename(old_name, &entry.name).and_then(|()| {
+            info!("Profile {:?} renamed to {:?}", old_name, entry.name);
+            // 重命名当前档案时档案目录也随之改变
+            if active
+                .0
+                .as_ref()
+                .is_some_and(|profile| &profile.name == old_name)
+            {
+                active.0 = Some(menu.store.open(&entry.name)?);
+            }
+            Ok(())
+        }),
+        None => menu.store.create(&entry.name).map(|_| {
+            info!("Profile {:?} created", entry.name);
+        }),
+    };
+    match result {
+        Ok(()) => {
+            menu.error = None;
+            menu.refresh();
+        }
+        Err(err) => menu.error = Some(format!("{:#}", err)),
+    }
+}
+
+/// 处理主菜单中当前档案的设置按钮，修改后立即保存到档案
+#[allow(clippy::type_complexity)]
+pub fn handle_profile_setting_button(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &ProfileSettingButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut active: ResMut<ActiveProfile>,
+    mut sim: ResMut<GameSimulation>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color, ProfileSettingButton(setting)) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = BackgroundColor(PRESSED_COLOR);
+
+                if *setting == ProfileSetting::Profile {
+                    info!("Switch profile pressed -> Switching to Profiles");
+                    next_state.set(GameState::Profiles);
+                    continue;
+                }
+
+                let Some(profile) = active.0.as_mut() else {
+                    continue;
+                };
+                match setting {
+                    ProfileSetting::Difficulty => {
+                        profile.settings.difficulty = profile.settings.difficulty.next();
+                        sim.set_extra_life_rule(profile.settings.difficulty.extra_life_rule());
+                    }
+                    ProfileSetting::KeyBindings => {
+                        profile.settings.key_bindings = profile.settings.key_bindings.next_preset();
+                    }
+                    ProfileSetting::Mode => {
+                        profile.settings.mode = profile.settings.mode.next();
+                    }
+                    ProfileSetting::Profile => {}
+                }
+                if let Err(err) = profile.save_settings() {
+                    error!("{:#}", err);
+                }
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(HOVERED_COLOR);
+            }
+            Interaction::None => {
+                *color = BackgroundColor(NONE_COLOR);
+            }
+        }
+    }
+}
+
+/// 新的一局按当前档案的难度设置奖励生命规则
+pub fn apply_difficulty(active: Res<ActiveProfile>, mut sim: ResMut<GameSimulation>) {
+    if let Some(profile) = &active.0 {
+        sim.set_extra_life_rule(profile.settings.difficulty.extra_life_rule());
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    ActiveProfile, GameSimulation, GameState, LevelEntityFilter, MAP_PATH, MapData,
+    PERSONAL_BEST_PREFIX, PersonalBest, PlayerInput, REPLAY_DIR, REPLAY_EXTENSION, Replay,
+    ReplayAction, Rewind, SimInput, reload_level, replay_path_from_args,
+};
+
+/// 正在录制的回放，播放回放时不录制
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct ReplayRecorder(pub Option<Replay>);
+
+impl ReplayRecorder {
+    /// 记录一个模拟帧的输入
+    pub fn record_input(&mut self, tick: u64, input: SimInput) {
+        if let Some(replay) = self.0.as_mut() {
+            replay.record_input(tick, input);
+        }
+    }
+
+    /// 记录重玩本关
+    pub fn record_restart(&mut self, tick: u64) {
+        if let Some(replay) = self.0.as_mut() {
+            replay.record_restart(tick);
+        }
+    }
+
+    /// 倒带后丢弃被撤销的输入
+    pub fn truncate(&mut self, tick: u64) {
+        if let Some(replay) = self.0.as_mut() {
+            replay.truncate(tick);
+        }
+    }
+}
+
+/// 正在播放的回放，存在时用回放中的输入代替键盘
+#[derive(Resource, Deref)]
+pub struct ReplayPlayback(pub Replay);
+
+/// 开始播放回放：按回放的地图、种子和规则重新开局
+pub fn start_playback(
+    commands: &mut Commands,
+    sim: &mut GameSimulation,
+    replay: Replay,
+) -> anyhow::Result<()> {
+    **sim = replay.simulation()?;
+    info!(
+        "Playing replay: map {}, seed {}, {} ticks",
+        replay.map_path.display(),
+        replay.seed,
+        replay.ticks
+    );
+    commands.insert_resource(sim.map().clone());
+    commands.insert_resource(ReplayPlayback(replay));
+    Ok(())
+}
+
+/// 档案的回放目录中最后保存的回放文件，不包括个人最佳回放
+pub fn latest_replay_path(profile_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(profile_dir.join(REPLAY_DIR))
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == REPLAY_EXTENSION))
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| !name.to_string_lossy().starts_with(PERSONAL_BEST_PREFIX))
+        })
+        .max_by_key(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+}
+
+/// 命令行指定了 `--replay` 时直接播放该回放
+pub fn load_replay_from_args(
+    mut commands: Commands,
+    mut sim: ResMut<GameSimulation>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if let Some(path) = replay_path_from_args(std::env::args()) {
+        play_replay_file(&mut commands, &mut sim, &mut next_state, &path);
+    }
+}
+
+/// 读取回放文件并直接进入游戏，地图等在进入游戏的状态切换中生成
+pub fn play_replay_file(
+    commands: &mut Commands,
+    sim: &mut GameSimulation,
+    next_state: &mut NextState<GameState>,
+    path: &Path,
+) {
+    match Replay::load(path).and_then(|replay| start_playback(commands, sim, replay)) {
+        Ok(()) => next_state.set(GameState::Playing),
+        Err(err) => error!("Failed to play replay {}: {:#}", path.display(), err),
+    }
+}
+
+/// 新的一局开始时开始录制
+pub fn start_recording(
+    sim: Res<GameSimulation>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    // 从存档继续的一局没有开头的输入，无法录制
+    **recorder = (playback.is_none() && sim.tick() == 0)
+        .then(|| Replay::new(MAP_PATH, sim.seed(), sim.extra_life_rule()));
+}
+
+/// 一局结束或放弃时把录制的回放保存到当前档案，超过个人最佳时同时保存为个人最佳
+///
+/// 用过倒带的一局只保存回放，不参与个人最佳
+pub fn save_replay(
+    sim: Res<GameSimulation>,
+    rewind: Res<Rewind>,
+    profile: Res<ActiveProfile>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut best: ResMut<PersonalBest>,
+) {
+    let Some(replay) = recorder.take() else {
+        return;
+    };
+    if replay.ticks == 0 {
+        return;
+    }
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    if let Some(dir) = profile.dir() {
+        let path = dir
+            .join(REPLAY_DIR)
+            .join(format!("{:010}.{}", secs, REPLAY_EXTENSION));
+        match replay.save(&path) {
+            Ok(()) => info!("Replay saved to {}", path.display()),
+            Err(err) => error!("{:#}", err),
+        }
+    }
+
+    if !rewind.used && best.update(profile.dir(), &replay, sim.score()) {
+        info!("New personal best: {}", sim.score());
+    }
+}
+
+/// 把回放中本帧的操作作为玩家输入
+pub fn play_replay_input(
+    mut commands: Commands,
+    playback: Res<ReplayPlayback>,
+    mut sim: ResMut<GameSimulation>,
+    mut map_data: ResMut<MapData>,
+    mut input: ResMut<PlayerInput>,
+    entity_query: Query<Entity, LevelEntityFilter>,
+) {
+    for action in playback.actions_at(sim.tick()) {
+        match action {
+            ReplayAction::Turn(dir) => input.direction = Some(dir),
+            ReplayAction::RestartLevel => {
+                sim.restart_level();
+                *map_data = sim.map().clone();
+                reload_level(&mut commands, &map_data, &entity_query);
+            }
+        }
+    }
+}
+
+/// 回放是否已经播放完
+pub fn replay_finished(sim: Res<GameSimulation>, playback: Option<Res<ReplayPlayback>>) -> bool {
+    playback.is_some_and(|playback| sim.tick() >= playback.ticks)
+}
+
+/// 回放播放完毕，进入结束画面
+pub fn end_replay(mut next_state: ResMut<NextState<GameState>>) {
+    info!("Replay finished -> Switching to GameOver");
+    next_state.set(GameState::GameOver);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+
+    use super::*;
+    use crate::{ExtraLifeRule, MapUI, StatsTracker, start_game_systems};
+
+    #[test]
+    fn test_cli_replay_spawns_map() {
+        let path = std::env::temp_dir().join(format!("pac-man-cli-{}.replay", std::process::id()));
+        let mut replay = Replay::new(MAP_PATH, 7, ExtraLifeRule::Disabled);
+        replay.ticks = 60;
+        replay.save(&path).unwrap();
+
+        // 命令行回放在启动时进入游戏，这时还在档案页面
+        let mut app = App::new();
+        let replay_path = path.clone();
+        app.add_plugins(StatesPlugin)
+            .init_state::<GameState>()
+            .init_resource::<GameSimulation>()
+            .init_resource::<ReplayRecorder>()
+            .init_resource::<PersonalBest>()
+            .init_resource::<StatsTracker>()
+            .add_systems(
+                Startup,
+                move |mut commands: Commands,
+                      mut sim: ResMut<GameSimulation>,
+                      mut next_state: ResMut<NextState<GameState>>| {
+                    play_replay_file(&mut commands, &mut sim, &mut next_state, &replay_path);
+                },
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Profiles,
+                    entered: GameState::Playing,
+                },
+                start_game_systems(),
+            );
+        app.update();
+        app.update();
+        fs::remove_file(&path).unwrap();
+
+        let world = app.world_mut();
+        assert_eq!(
+            world.resource::<State<GameState>>().get(),
+            &GameState::Playing
+        );
+        assert!(world.contains_resource::<ReplayPlayback>());
+        assert_eq!(
+            world
+                .query_filtered::<(), With<MapUI>>()
+                .iter(world)
+                .count(),
+            1
+        );
+    }
+}
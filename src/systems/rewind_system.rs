@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::{
+    ActiveProfile, GameSimulation, GhostRun, LevelEntityFilter, MapData, PlayMode, ReplayRecorder,
+    RewindBuffer, SimSnapshot, reload_level,
+};
+
+/// 倒带状态：按住 R 键让整局游戏逐帧倒退
+///
+/// 倒带只在休闲模式下可用，排位模式中按 R 键没有作用；
+/// 休闲模式中用过倒带的一局不计入最高分、个人最佳、统计和成就
+#[derive(Resource, Default)]
+pub struct Rewind {
+    /// 每个模拟帧之前的快照，同时保存个人最佳回放的进度
+    buffer: RewindBuffer<(SimSnapshot, Option<SimSnapshot>)>,
+    /// 本帧是否按住倒带键
+    pub active: bool,
+    /// 本局是否用过倒带
+    pub used: bool,
+}
+
+impl Rewind {
+    /// 新的一局开始时清空快照和倒带记录
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.active = false;
+        self.used = false;
+    }
+}
+
+/// 读取倒带键，与玩家输入一样在渲染帧中采集，排位模式下不能倒带
+pub fn read_rewind_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    profile: Res<ActiveProfile>,
+    mut rewind: ResMut<Rewind>,
+) {
+    let mode = profile
+        .0
+        .as_ref()
+        .map_or(PlayMode::default(), |profile| profile.settings.mode);
+    rewind.active = mode.allows_rewind() && keyboard.pressed(KeyCode::KeyR);
+}
+
+/// 是否正在倒带
+pub fn rewinding(rewind: Res<Rewind>) -> bool {
+    rewind.active
+}
+
+/// 本局是否没有用过倒带，只有这样的一局才能刷新最高分
+pub fn rewind_unused(rewind: Res<Rewind>) -> bool {
+    !rewind.used
+}
+
+/// 新的一局开始时清空倒带记录
+pub fn reset_rewind(mut rewind: ResMut<Rewind>) {
+    rewind.reset();
+}
+
+/// 推进模拟帧之前保存快照
+pub fn record_rewind_snapshot(
+    sim: Res<GameSimulation>,
+    ghost_run: Option<Res<GhostRun>>,
+    mut rewind: ResMut<Rewind>,
+) {
+    let ghost_snapshot = ghost_run.map(|ghost_run| ghost_run.snapshot());
+    rewind.buffer.push((sim.snapshot(), ghost_snapshot));
+}
+
+/// 倒回一个模拟帧，地图变化时重新生成实体，录制的回放丢弃被撤销的输入
+pub fn rewind_simulation(
+    mut commands: Commands,
+    mut rewind: ResMut<Rewind>,
+    mut sim: ResMut<GameSimulation>,
+    mut ghost_run: Option<ResMut<GhostRun>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut map_data: ResMut<MapData>,
+    entity_query: Query<Entity, LevelEntityFilter>,
+) {
+    let Some((snapshot, ghost_snapshot)) = rewind.buffer.pop() else {
+        return;
+    };
+    if !rewind.used {
+        info!("Rewind used -> this run no longer counts for high scores");
+        rewind.used = true;
+    }
+
+    sim.restore(&snapshot);
+    if let (Some(ghost_run), Some(ghost_snapshot)) = (ghost_run.as_mut(), ghost_snapshot) {
+        ghost_run.restore(&ghost_snapshot);
+    }
+    recorder.truncate(sim.tick());
+
+    // 吃掉的豆子和关卡都可能倒回去，地图不同时整关重新生成
+    if *map_data != *sim.map() {
+        *map_data = sim.map().clone();
+        reload_level(&mut commands, &map_data, &entity_query);
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{prelude::*, window::WindowCloseRequested};
+
+use crate::{
+    ActiveProfile, GameSimulation, GameState, MAP_PATH, Rewind, SAVE_DIR, SAVE_SLOTS, SaveGame,
+    Simulation,
+};
+
+/// 当前档案的存档槽位，每个槽位保存一局未完成的游戏
+#[derive(Resource, Default)]
+pub struct SaveSlots {
+    /// 每个槽位的存档，空槽位为 None
+    pub slots: [Option<SaveGame>; SAVE_SLOTS],
+    /// 当前这一局使用的槽位，观看回放时为 None
+    pub active: Option<usize>,
+}
+
+/// 需要再点一次确认的存档槽位操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotConfirm {
+    /// 槽位都满时开始新的一局会覆盖这个槽位
+    Overwrite(usize),
+    /// 删除这个槽位的存档
+    Delete(usize),
+}
+
+/// 主菜单中存档槽位的状态，变化时重新生成菜单
+#[derive(Resource, Default, PartialEq)]
+pub struct SaveSlotMenu {
+    pub confirm: Option<SlotConfirm>,
+}
+
+impl SaveSlotMenu {
+    /// 新的一局使用的槽位：第一个空槽位，都满时要先确认覆盖最早的存档
+    ///
+    /// ## Return
+    /// 还没有确认覆盖时返回 None，并等待确认
+    pub fn new_game_slot(&mut self, slots: &SaveSlots) -> Option<usize> {
+        if let Some(slot) = slots.free_slot() {
+            return Some(slot);
+        }
+        let oldest = slots.oldest_slot();
+        if self.confirm == Some(SlotConfirm::Overwrite(oldest)) {
+            return Some(oldest);
+        }
+        self.confirm = Some(SlotConfirm::Overwrite(oldest));
+        None
+    }
+}
+
+impl SaveSlots {
+    /// 槽位对应的存档文件
+    pub fn path(profile_dir: &Path, slot: usize) -> PathBuf {
+        profile_dir
+            .join(SAVE_DIR)
+            .join(format!("slot-{}.ron", slot + 1))
+    }
+
+    /// 第一个空槽位，都满时为 None
+    pub fn free_slot(&self) -> Option<usize> {
+        self.slots.iter().position(Option::is_none)
+    }
+
+    /// 存档最早的槽位，槽位都满时建议覆盖这个
+    pub fn oldest_slot(&self) -> usize {
+        (0..SAVE_SLOTS)
+            .min_by_key(|&slot| self.slots[slot].as_ref().map_or(0, |save| save.saved_at))
+            .unwrap_or(0)
+    }
+
+    /// 把当前这一局保存到当前槽位
+    pub fn save(&mut self, profile_dir: &Path, sim: &Simulation, rewind_used: bool) {
+        let Some(slot) = self.active else {
+            return;
+        };
+
+        let saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let save = sim.to_save(Path::new(MAP_PATH), saved_at, rewind_used);
+
+        let path = Self::path(profile_dir, slot);
+        match save.save(&path) {
+            Ok(()) => {
+                info!("Game saved to slot {} ({})", slot + 1, path.display());
+                self.slots[slot] = Some(save);
+            }
+            Err(err) => error!("{:#}", err),
+        }
+    }
+
+    /// 删除槽位的存档
+    pub fn delete(&mut self, profile_dir: &Path, slot: usize) {
+        if self.slots[slot].take().is_some()
+            && let Err(err) = fs::remove_file(Self::path(profile_dir, slot))
+        {
+            error!("Failed to delete save slot {}: {}", slot + 1, err);
+        }
+    }
+
+    /// 一局结束后清空当前槽位
+    pub fn clear_active(&mut self, profile_dir: &Path) {
+        if let Some(slot) = self.active {
+            self.delete(profile_dir, slot);
+        }
+    }
+}
+
+/// 选择档案后读取该档案的所有存档槽位
+pub fn load_save_slots(profile: Res<ActiveProfile>, mut slots: ResMut<SaveSlots>) {
+    *slots = SaveSlots::default();
+    let Some(dir) = profile.dir() else {
+        return;
+    };
+
+    for slot in 0..SAVE_SLOTS {
+        let path = SaveSlots::path(dir, slot);
+        if !path.exists() {
+            continue;
+        }
+        match SaveGame::load(&path) {
+            Ok(save) => slots.slots[slot] = Some(save),
+            Err(err) => error!("{:#}", err),
+        }
+    }
+}
+
+/// 离开主菜单时取消等待确认的槽位操作
+pub fn reset_save_slot_menu(mut slot_menu: ResMut<SaveSlotMenu>) {
+    slot_menu.set_if_neq(SaveSlotMenu::default());
+}
+
+/// 从存档槽位继续游戏
+pub fn resume_game(
+    commands: &mut Commands,
+    sim: &mut GameSimulation,
+    rewind: &mut Rewind,
+    slots: &mut SaveSlots,
+    slot: usize,
+) -> anyhow::Result<()> {
+    let Some(save) = slots.slots[slot].clone() else {
+        anyhow::bail!("Save slot {} is empty", slot + 1);
+    };
+
+    // 用过倒带的一局继续后仍然不计入最高分
+    rewind.reset();
+    rewind.used = save.rewind_used;
+    **sim = Simulation::from_save(save)?;
+    commands.insert_resource(sim.map().clone());
+    slots.active = Some(slot);
+    Ok(())
+}
+
+/// 中途回到主菜单时保存当前这一局
+pub fn save_game(
+    profile: Res<ActiveProfile>,
+    sim: Res<GameSimulation>,
+    rewind: Res<Rewind>,
+    mut slots: ResMut<SaveSlots>,
+) {
+    if let Some(dir) = profile.dir() {
+        slots.save(dir, &sim, rewind.used);
+    }
+}
+
+/// 关闭窗口时自动保存正在进行的一局
+pub fn save_on_window_close(
+    mut reader: MessageReader<WindowCloseRequested>,
+    state: Res<State<GameState>>,
+    profile: Res<ActiveProfile>,
+    sim: Res<GameSimulation>,
+    rewind: Res<Rewind>,
+    mut slots: ResMut<SaveSlots>,
+) {
+    if reader.read().count() == 0 {
+        return;
+    }
+    if let Some(dir) = profile.dir()
+        && matches!(state.get(), GameState::Playing | GameState::Paused)
+    {
+        slots.save(dir, &sim, rewind.used);
+    }
+}
+
+/// 游戏结束时删除这一局的存档
+pub fn clear_save_slot(profile: Res<ActiveProfile>, mut slots: ResMut<SaveSlots>) {
+    if let Some(dir) = profile.dir() {
+        slots.clear_active(dir);
+    }
+}
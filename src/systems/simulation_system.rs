@@ -0,0 +1,152 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    EatPelletEvent, ExtraLifeEvent, FruitEatenEvent, GameState, Ghost, GhostEatenEvent, Level,
+    LevelClearedEvent, Lives, MapData, Player, PlayerDeathEvent, PlayerInput, ReplayRecorder,
+    Score, SimEvent, SimInput, Simulation, default_seed, read_map_data,
+};
+
+/// 当前这一局的模拟状态，Bevy 中的实体和资源都从这里同步
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameSimulation(pub Simulation);
+
+impl Default for GameSimulation {
+    fn default() -> Self {
+        let seed = default_seed();
+        info!("Game RNG seed: {}", seed);
+        Self(Simulation::new(read_map_data(), seed))
+    }
+}
+
+/// 模拟事件转换成的 Bevy 消息
+#[derive(SystemParam)]
+pub struct SimMessages<'w> {
+    pellet: MessageWriter<'w, EatPelletEvent>,
+    fruit: MessageWriter<'w, FruitEatenEvent>,
+    ghost: MessageWriter<'w, GhostEatenEvent>,
+    death: MessageWriter<'w, PlayerDeathEvent>,
+    extra_life: MessageWriter<'w, ExtraLifeEvent>,
+    cleared: MessageWriter<'w, LevelClearedEvent>,
+}
+
+/// 推进一个模拟帧，把模拟事件转换成 Bevy 消息，同时录制本帧的输入
+pub fn step_simulation(
+    mut sim: ResMut<GameSimulation>,
+    mut input: ResMut<PlayerInput>,
+    mut map_data: ResMut<MapData>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut messages: SimMessages,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let input = SimInput {
+        direction: input.direction.take(),
+    };
+    recorder.record_input(sim.tick(), input);
+    let events = sim.step(input);
+
+    let mut map_changed = false;
+    for evt in events {
+        match evt {
+            SimEvent::PelletEaten {
+                position,
+                pellet_type,
+            } => {
+                map_changed = true;
+                messages.pellet.write(EatPelletEvent {
+                    position,
+                    pellet_type,
+                });
+            }
+            SimEvent::FruitSpawned {
+                position,
+                fruit_type,
+            } => {
+                info!("Spawning {:?} at {}", fruit_type, position);
+            }
+            SimEvent::FruitEaten {
+                position,
+                fruit_type,
+                points,
+            } => {
+                messages.fruit.write(FruitEatenEvent {
+                    position,
+                    fruit_type,
+                    points,
+                });
+            }
+            SimEvent::FruitExpired { .. } => {}
+            SimEvent::GhostReleased { ghost_type } => {
+                info!("{:?} leaves the ghost house", ghost_type);
+            }
+            SimEvent::GhostModeChanged { mode } => {
+                info!("Ghosts switch to {:?}", mode);
+            }
+            SimEvent::GhostEaten {
+                position,
+                ghost_type,
+                points,
+            } => {
+                info!("Ate {:?} for {} points", ghost_type, points);
+                messages.ghost.write(GhostEatenEvent {
+                    position,
+                    ghost_type,
+                    points,
+                });
+            }
+            SimEvent::PlayerDied {
+                position,
+                ghost_type,
+            } => {
+                info!("Player caught by {:?} at {}", ghost_type, position);
+                messages.death.write(PlayerDeathEvent {
+                    position,
+                    ghost_type,
+                });
+            }
+            SimEvent::ExtraLife { lives, score } => {
+                info!("Extra life at {} points, lives: {}", score, lives);
+                messages.extra_life.write(ExtraLifeEvent { lives, score });
+            }
+            SimEvent::LevelCleared { level } => {
+                info!("Level {} cleared", level);
+                map_changed = true;
+                messages.cleared.write(LevelClearedEvent { level });
+            }
+            SimEvent::GameOver => {
+                info!("No lives left -> Switching to GameOver");
+                next_state.set(GameState::GameOver);
+            }
+        }
+    }
+
+    if map_changed {
+        *map_data = sim.map().clone();
+    }
+}
+
+/// 把模拟中的角色、分数、生命和关卡同步到 Bevy 的组件和资源
+pub fn sync_simulation(
+    sim: Res<GameSimulation>,
+    mut player_query: Query<&mut Player>,
+    mut ghost_query: Query<&mut Ghost>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    mut level: ResMut<Level>,
+) {
+    for mut player in &mut player_query {
+        player.clone_from(sim.player());
+    }
+    for mut ghost in &mut ghost_query {
+        if let Some(state) = sim
+            .ghosts()
+            .iter()
+            .find(|state| state.ghost_type == ghost.ghost_type)
+        {
+            ghost.clone_from(state);
+        }
+    }
+
+    score.value = sim.score();
+    lives.clone_from(sim.lives());
+    level.clone_from(sim.level());
+}
@@ -0,0 +1,98 @@
+use bevy::{ecs::system::SystemParam, prelude::*, window::WindowCloseRequested};
+
+use crate::{
+    ActiveProfile, EatPelletEvent, GameSimulation, GhostEatenEvent, LevelClearedEvent,
+    LifetimeStats, PlayerDeathEvent, SIMULATION_HZ,
+};
+
+/// 统计用的本局状态，不保存
+#[derive(Resource, Default)]
+pub struct StatsTracker {
+    /// 当前这一关开始时的模拟帧，从存档继续时无法得知
+    level_start_tick: Option<u64>,
+}
+
+/// 统计读取的游戏消息
+#[derive(SystemParam)]
+pub struct StatsMessages<'w, 's> {
+    pellet: MessageReader<'w, 's, EatPelletEvent>,
+    ghost: MessageReader<'w, 's, GhostEatenEvent>,
+    death: MessageReader<'w, 's, PlayerDeathEvent>,
+    cleared: MessageReader<'w, 's, LevelClearedEvent>,
+}
+
+/// 选择档案后读取该档案的累计统计
+pub fn load_stats(profile: Res<ActiveProfile>, mut stats: ResMut<LifetimeStats>) {
+    *stats = LifetimeStats::default();
+    let Some(dir) = profile.dir() else {
+        return;
+    };
+    let path = LifetimeStats::path(dir);
+    if !path.exists() {
+        return;
+    }
+
+    match LifetimeStats::load(&path) {
+        Ok(loaded) => *stats = loaded,
+        Err(err) => error!("{:#}", err),
+    }
+}
+
+/// 保存累计统计到档案目录
+pub fn save_stats(profile: Res<ActiveProfile>, stats: Res<LifetimeStats>) {
+    let Some(dir) = profile.dir() else {
+        return;
+    };
+    if let Err(err) = stats.save(&LifetimeStats::path(dir)) {
+        error!("{:#}", err);
+    }
+}
+
+/// 关闭窗口时保存累计统计
+pub fn save_stats_on_window_close(
+    mut reader: MessageReader<WindowCloseRequested>,
+    profile: Res<ActiveProfile>,
+    stats: Res<LifetimeStats>,
+) {
+    if reader.read().count() > 0 {
+        save_stats(profile, stats);
+    }
+}
+
+/// 新的一局开始时记录第一关开始的模拟帧
+pub fn start_stats_tracking(sim: Res<GameSimulation>, mut tracker: ResMut<StatsTracker>) {
+    tracker.level_start_tick = (sim.tick() == 0).then_some(0);
+}
+
+/// 从本帧的游戏消息中累计统计
+///
+/// 观看回放时不统计；倒带后重玩的部分会再次产生同样的消息，
+/// 所以用过倒带后这一局不再统计
+pub fn record_stats(
+    mut messages: StatsMessages,
+    sim: Res<GameSimulation>,
+    mut tracker: ResMut<StatsTracker>,
+    mut stats: ResMut<LifetimeStats>,
+) {
+    for evt in messages.pellet.read() {
+        stats.record_pellet(evt.pellet_type);
+    }
+    for evt in messages.ghost.read() {
+        stats.record_ghost_eaten(evt.ghost_type);
+    }
+    for evt in messages.death.read() {
+        stats.record_death(evt.ghost_type);
+    }
+    for _ in messages.cleared.read() {
+        let duration = tracker
+            .level_start_tick
+            .map(|start| (sim.tick().saturating_sub(start) as f64 / SIMULATION_HZ) as f32);
+        stats.record_level_cleared(duration);
+        tracker.level_start_tick = Some(sim.tick());
+    }
+}
+
+/// 累计游戏时间，暂停和观看回放时不计时
+pub fn track_play_time(time: Res<Time>, mut stats: ResMut<LifetimeStats>) {
+    stats.play_time += time.delta_secs_f64();
+}
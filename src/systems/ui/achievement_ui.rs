@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::{
+    ACHIEVEMENT_TOAST_COLOR, ACHIEVEMENT_TOAST_DURATION, AchievementUnlockedEvent,
+    BUTTON_TEXT_COLOR, TITLE_COLOR,
+    localization::LanguageSettings,
+    systems::fonts::{FontAssets, get_font_for_language},
+};
+
+/// 解锁成就的提示，显示一段时间后消失
+#[derive(Component)]
+pub struct AchievementToast {
+    pub remaining_time: f32,
+}
+
+/// 解锁成就时在屏幕下方弹出提示，同时解锁多个时依次向上排列
+pub fn spawn_achievement_toasts(
+    mut commands: Commands,
+    mut reader: MessageReader<AchievementUnlockedEvent>,
+    query: Query<(), With<AchievementToast>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    let font = get_font_for_language(&language_settings, &font_assets);
+    let texts = language_settings.get_texts();
+
+    for (i, evt) in reader.read().enumerate() {
+        let index = query.iter().count() + i;
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(16.0 + index as f32 * 72.0),
+                    left: Val::Percent(50.0),
+                    width: Val::Px(360.0),
+                    margin: UiRect::left(Val::Px(-180.0)),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(ACHIEVEMENT_TOAST_COLOR),
+                GlobalZIndex(2),
+                AchievementToast {
+                    remaining_time: ACHIEVEMENT_TOAST_DURATION,
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(format!("{}: {}", texts.achievement_unlocked, evt.name)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(TITLE_COLOR),
+                ));
+                parent.spawn((
+                    Text::new(evt.description.clone()),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(BUTTON_TEXT_COLOR),
+                ));
+            });
+    }
+}
+
+/// 提示计时结束后移除，暂停时也照常计时
+pub fn update_achievement_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut query: Query<(Entity, &mut AchievementToast)>,
+) {
+    for (entity, mut toast) in &mut query {
+        toast.remaining_time -= time.delta_secs();
+        if toast.remaining_time <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+
+use crate::{
+    BUTTON_TEXT_COLOR, BestScore, InitialsEntry, Level, MenuButton, OVERLAY_COLOR,
+    QUIT_BUTTON_BORDER_COLOR, START_BUTTON_BORDER_COLOR, Score, TITLE_COLOR,
+    localization::LanguageSettings,
+    spawn_button,
+    systems::fonts::{FontAssets, get_font_for_language},
+};
+
+#[derive(Component)]
+pub struct GameOverUI;
+
+#[derive(Component)]
+pub struct RetryButton;
+
+/// 输入缩写的文本
+#[derive(Component)]
+pub struct InitialsText;
+
+/// 设置游戏结束UI，显示最终分数、到达的关卡和最高分，能上榜时输入缩写
+pub fn setup_game_over_ui(
+    mut commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    score: Res<Score>,
+    level: Res<Level>,
+    best_score: Res<BestScore>,
+    initials: Option<Res<InitialsEntry>>,
+) {
+    let font = get_font_for_language(&language_settings, &font_assets);
+    let texts = language_settings.get_texts();
+
+    let stats = [
+        format!("{}: {}", texts.score_label, score.value),
+        format!("{}: {}", texts.level_label, level.value),
+        format!("{}: {}", texts.best_score_label, best_score.value),
+    ];
+
+    // 根UI容器
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(OVERLAY_COLOR),
+            GlobalZIndex(1),
+            GameOverUI,
+        ))
+        .with_children(|parent| {
+            // 游戏结束标题
+            parent.spawn((
+                Text::new(texts.game_over_title),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 60.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            // 本局统计
+            for line in stats {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        ..default()
+                    },
+                    TextColor(BUTTON_TEXT_COLOR),
+                    Node {
+                        margin: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                ));
+            }
+
+            // 上榜时输入缩写
+            if let Some(initials) = initials {
+                parent.spawn((
+                    Text::new(texts.new_high_score),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 32.0,
+                        ..default()
+                    },
+                    TextColor(TITLE_COLOR),
+                    Node {
+                        margin: UiRect::top(Val::Px(12.0)),
+                        ..default()
+                    },
+                ));
+                parent.spawn((
+                    Text::new(initials_line(&initials, texts.enter_initials)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 32.0,
+                        ..default()
+                    },
+                    TextColor(BUTTON_TEXT_COLOR),
+                    InitialsText,
+                ));
+                parent.spawn((
+                    Text::new(texts.initials_hint),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(BUTTON_TEXT_COLOR),
+                ));
+            }
+
+            spawn_button(
+                parent,
+                texts.retry_button,
+                &font,
+                START_BUTTON_BORDER_COLOR,
+                RetryButton,
+            );
+            spawn_button(
+                parent,
+                texts.menu_button,
+                &font,
+                QUIT_BUTTON_BORDER_COLOR,
+                MenuButton,
+            );
+        });
+}
+
+/// 刷新输入中的缩写
+pub fn update_initials_ui(
+    initials: Res<InitialsEntry>,
+    language_settings: Res<LanguageSettings>,
+    mut query: Query<&mut Text, With<InitialsText>>,
+) {
+    let texts = language_settings.get_texts();
+    for mut text in &mut query {
+        text.0 = initials_line(&initials, texts.enter_initials);
+    }
+}
+
+/// 缩写的显示文本：输入中的字母带方括号，确认后显示名次
+fn initials_line(initials: &InitialsEntry, label: &str) -> String {
+    if let Some(rank) = initials.rank {
+        return format!("#{} {}", rank + 1, initials.initials());
+    }
+
+    let letters: Vec<String> = initials
+        .letters
+        .iter()
+        .enumerate()
+        .map(|(i, &letter)| {
+            if i == initials.cursor {
+                format!("[{}]", letter as char)
+            } else {
+                format!(" {} ", letter as char)
+            }
+        })
+        .collect();
+    format!("{}: {}", label, letters.concat())
+}
+
+pub fn cleanup_game_over_ui(mut commands: Commands, query: Query<Entity, With<GameOverUI>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
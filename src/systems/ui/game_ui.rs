@@ -1,9 +1,13 @@
+use std::path::PathBuf;
+
 use bevy::prelude::*;
 
 use crate::{
-    BACKGROUND_COLOR, BUTTON_TEXT_COLOR, QUIT_BUTTON_BORDER_COLOR, START_BUTTON_BORDER_COLOR,
-    TITLE_COLOR,
+    ActiveProfile, BACKGROUND_COLOR, BUTTON_TEXT_COLOR, QUIT_BUTTON_BORDER_COLOR,
+    REPLAY_BUTTON_BORDER_COLOR, RESTART_BUTTON_BORDER_COLOR, START_BUTTON_BORDER_COLOR,
+    SaveSlotMenu, SaveSlots, SlotConfirm, TITLE_COLOR, latest_replay_path,
     localization::LanguageSettings,
+    spawn_profile_settings,
     systems::fonts::{FontAssets, get_font_for_language},
 };
 
@@ -13,6 +17,24 @@ pub struct MenuUI;
 #[derive(Component)]
 pub struct StartButton;
 
+/// 从存档槽位继续游戏，值为槽位下标
+#[derive(Component)]
+pub struct ContinueButton(pub usize);
+
+/// 删除存档槽位，值为槽位下标
+#[derive(Component)]
+pub struct DeleteSaveButton(pub usize);
+
+/// 观看回放，值为回放文件
+#[derive(Component)]
+pub struct ReplayButton(pub PathBuf);
+
+#[derive(Component)]
+pub struct HighScoresButton;
+
+#[derive(Component)]
+pub struct StatisticsButton;
+
 #[derive(Component)]
 pub struct QuitButton;
 
@@ -21,9 +43,20 @@ pub fn setup_menu_ui(
     mut commands: Commands,
     language_settings: Res<LanguageSettings>,
     font_assets: Res<FontAssets>,
+    slots: Res<SaveSlots>,
+    slot_menu: Res<SaveSlotMenu>,
+    active: Res<ActiveProfile>,
 ) {
     let font = get_font_for_language(&language_settings, &font_assets);
     let texts = language_settings.get_texts();
+    // 槽位都满时 Start 按钮提示将要覆盖的槽位
+    let (start_text, start_font_size) = match slot_menu.confirm {
+        Some(SlotConfirm::Overwrite(slot)) => (
+            format!("{} {}?", texts.overwrite_slot_button, slot + 1),
+            20.0,
+        ),
+        _ => (texts.start_button.to_string(), 30.0),
+    };
 
     // 根UI容器
     commands
@@ -40,6 +73,9 @@ pub fn setup_menu_ui(
             MenuUI,
         ))
         .with_children(|parent| {
+            // 当前档案的设置
+            spawn_profile_settings(parent, &active, texts, &font);
+
             // 游戏标题
             parent.spawn((
                 Text::new(texts.game_title),
@@ -69,10 +105,172 @@ pub fn setup_menu_ui(
                 ))
                 .with_children(|btn| {
                     btn.spawn((
-                        Text::new(texts.start_button),
+                        Text::new(start_text),
                         TextFont {
                             font: font.clone(),
-                            font_size: 30.0,
+                            font_size: start_font_size,
+                            ..default()
+                        },
+                        TextColor(BUTTON_TEXT_COLOR),
+                    ));
+                });
+
+            // 每个有存档的槽位一行 Continue 和 Delete 按钮
+            for (slot, save) in slots.slots.iter().enumerate() {
+                let Some(save) = save else {
+                    continue;
+                };
+                let delete_text = if slot_menu.confirm == Some(SlotConfirm::Delete(slot)) {
+                    texts.confirm_delete_button
+                } else {
+                    texts.delete_button
+                };
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::bottom(Val::Px(10.0)),
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(320.0),
+                                height: Val::Px(50.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BorderColor::all(RESTART_BUTTON_BORDER_COLOR),
+                            ContinueButton(slot),
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn((
+                                Text::new(format!(
+                                    "{} {}: {} {} - {}",
+                                    texts.continue_button,
+                                    slot + 1,
+                                    texts.level_label,
+                                    save.level.value,
+                                    save.score
+                                )),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(BUTTON_TEXT_COLOR),
+                            ));
+                        });
+                        row.spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(110.0),
+                                height: Val::Px(50.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BorderColor::all(QUIT_BUTTON_BORDER_COLOR),
+                            DeleteSaveButton(slot),
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn((
+                                Text::new(delete_text),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(BUTTON_TEXT_COLOR),
+                            ));
+                        });
+                    });
+            }
+
+            // 当前档案有回放时显示 Watch Replay 按钮
+            if let Some(path) = active.dir().and_then(latest_replay_path) {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(200.0),
+                            height: Val::Px(65.0),
+                            margin: UiRect::bottom(Val::Px(10.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        BorderColor::all(REPLAY_BUTTON_BORDER_COLOR),
+                        ReplayButton(path),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            Text::new(texts.replay_button),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 24.0,
+                                ..default()
+                            },
+                            TextColor(BUTTON_TEXT_COLOR),
+                        ));
+                    });
+            }
+
+            // High Scores 按钮
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(50.0),
+                        margin: UiRect::bottom(Val::Px(10.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor::all(TITLE_COLOR),
+                    HighScoresButton,
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new(texts.high_scores_button),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        TextColor(BUTTON_TEXT_COLOR),
+                    ));
+                });
+
+            // Statistics 按钮
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(50.0),
+                        margin: UiRect::bottom(Val::Px(10.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor::all(TITLE_COLOR),
+                    StatisticsButton,
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new(texts.statistics_button),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 24.0,
                             ..default()
                         },
                         TextColor(BUTTON_TEXT_COLOR),
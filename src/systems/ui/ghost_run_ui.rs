@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use crate::{
+    GHOST_RUN_AHEAD_COLOR, GHOST_RUN_ALPHA, GHOST_RUN_BEHIND_COLOR, GHOST_RUN_HUD_FONT_SIZE,
+    GameSimulation, GhostRun, GhostRunSettings, MapData, PLAYER_COLOR, PLAYER_RADIUS, Z_GHOST_RUN,
+    ghost_run_delta, grid_to_world,
+    localization::LanguageSettings,
+    systems::fonts::{FontAssets, get_font_for_language},
+};
+
+/// 个人最佳回放的所有实体，随关卡一起清理
+#[derive(Component)]
+pub struct GhostRunUI;
+
+/// 个人最佳回放中的半透明吃豆人
+#[derive(Component)]
+pub struct GhostRunPlayerUI;
+
+/// 与个人最佳比较的 HUD
+#[derive(Component)]
+pub struct GhostRunHud;
+
+/// 有个人最佳回放时生成半透明吃豆人和 HUD
+pub fn spawn_ghost_run_ui(
+    mut commands: Commands,
+    ghost_run: Option<Res<GhostRun>>,
+    query: Query<(), With<GhostRunUI>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if ghost_run.is_none() || !query.is_empty() {
+        return;
+    }
+
+    let circle = shapes::Circle {
+        radius: PLAYER_RADIUS,
+        center: Vec2::ZERO,
+    };
+    commands.spawn((
+        ShapeBuilder::with(&circle)
+            .fill(Fill::color(PLAYER_COLOR.with_alpha(GHOST_RUN_ALPHA)))
+            .build(),
+        Transform::from_xyz(0.0, 0.0, Z_GHOST_RUN),
+        Visibility::Hidden,
+        GhostRunUI,
+        GhostRunPlayerUI,
+    ));
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            left: Val::Px(8.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font: get_font_for_language(&language_settings, &font_assets),
+            font_size: GHOST_RUN_HUD_FONT_SIZE,
+            ..default()
+        },
+        TextColor(GHOST_RUN_AHEAD_COLOR),
+        Visibility::Hidden,
+        GhostRunUI,
+        GhostRunHud,
+    ));
+}
+
+/// 同步个人最佳回放中吃豆人的位置，回放结束后隐藏
+pub fn update_ghost_run_ui(
+    ghost_run: Res<GhostRun>,
+    settings: Res<GhostRunSettings>,
+    map_data: Res<MapData>,
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &mut Visibility), With<GhostRunPlayerUI>>,
+) {
+    // 与玩家一样在两个模拟帧之间插值
+    let overstep = fixed_time.overstep().as_secs_f32();
+    let sim = ghost_run.sim();
+    for (mut transform, mut visibility) in &mut query {
+        let visible = settings.enabled && !ghost_run.is_finished();
+        *visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        let world = grid_to_world(&map_data, sim.player().position(sim.map(), overstep));
+        transform.translation = world.extend(Z_GHOST_RUN);
+    }
+}
+
+/// 刷新 HUD：领先或落后个人最佳多少豆子和分数
+pub fn update_ghost_run_hud(
+    ghost_run: Res<GhostRun>,
+    settings: Res<GhostRunSettings>,
+    sim: Res<GameSimulation>,
+    language_settings: Res<LanguageSettings>,
+    mut query: Query<(&mut Text, &mut TextColor, &mut Visibility), With<GhostRunHud>>,
+) {
+    let texts = language_settings.get_texts();
+    let (pellets, score) = ghost_run_delta(&sim, &ghost_run);
+
+    for (mut text, mut color, mut visibility) in &mut query {
+        *visibility = if settings.enabled {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        text.0 = format!(
+            "{}  {} {:+}  {} {:+}",
+            texts.ghost_run_label, texts.pellets_label, pellets, texts.score_label, score
+        );
+        color.0 = if score >= 0 {
+            GHOST_RUN_AHEAD_COLOR
+        } else {
+            GHOST_RUN_BEHIND_COLOR
+        };
+    }
+}
@@ -4,10 +4,10 @@ use bevy_prototype_lyon::prelude::*;
 
 use crate::{
     BLINKY_COLOR, CLYDE_COLOR, GHOST_EYE_PUPIL_COLOR, GHOST_EYE_RADIUS, GHOST_EYE_WHITE_COLOR,
-    GHOST_FRIGHTENED_COLOR, GHOST_FRIGHTENED_FLASH_COLOR, GHOST_LEFT_EYE_POSITION,
-    GHOST_PUPIL_RADIUS, GHOST_RADIUS, GHOST_RIGHT_EYE_POSITION, GHOST_STROKE_COLOR, Ghost,
-    GhostMode, GhostType, HALF, INKY_COLOR, PINKY_COLOR, TILE_SIZE, Z_GHOST_BODY,
-    Z_GHOST_EYE_PUPIL, Z_GHOST_EYE_WHITE,
+    GHOST_FRIGHTENED_COLOR, GHOST_FRIGHTENED_FLASH_COLOR, GHOST_FRIGHTENED_FLASH_INTERVAL,
+    GHOST_LEFT_EYE_POSITION, GHOST_PUPIL_RADIUS, GHOST_RADIUS, GHOST_RIGHT_EYE_POSITION,
+    GHOST_STROKE_COLOR, Ghost, GhostMode, GhostType, INKY_COLOR, PINKY_COLOR, Z_GHOST_BODY,
+    Z_GHOST_EYE_PUPIL, Z_GHOST_EYE_WHITE, grid_to_world,
 };
 
 /// 幽灵UI标记组件
@@ -20,6 +20,8 @@ pub struct GhostRenderer {
     pub ghost_type: GhostType,
     pub is_frightened: bool,
     pub frightened_timer: f32,
+    /// 恐惧结束前的闪烁次数
+    pub frightened_flashes: u32,
 }
 
 impl GhostRenderer {
@@ -28,14 +30,17 @@ impl GhostRenderer {
             ghost_type,
             is_frightened: false,
             frightened_timer: 0.0,
+            frightened_flashes: 0,
         }
     }
 
     /// 获取幽灵颜色
     pub fn get_color(&self) -> Color {
         if self.is_frightened {
-            // 恐惧状态：蓝白闪烁
-            if self.frightened_timer > 2.0 || (self.frightened_timer * 10.0).floor() as i32 % 2 == 0
+            // 恐惧状态：最后几次闪烁时蓝白交替，每次闪烁蓝白各一个间隔
+            let flash_time = self.frightened_flashes as f32 * 2.0 * GHOST_FRIGHTENED_FLASH_INTERVAL;
+            if self.frightened_timer > flash_time
+                || (self.frightened_timer / GHOST_FRIGHTENED_FLASH_INTERVAL).floor() as i32 % 2 == 0
             {
                 GHOST_FRIGHTENED_COLOR
             } else {
@@ -69,24 +74,19 @@ pub fn spawn_ghost_ui(
     map_data: Res<crate::MapData>,
     query: Query<(Entity, &Ghost), Without<GhostUI>>,
 ) {
-    // 计算地图偏移量（与世界坐标系一致）
-    let offset_x = -((map_data.width as f32) * TILE_SIZE) / 2.0;
-    let offset_y = ((map_data.height as f32) * TILE_SIZE) / 2.0;
-
     for (entity, ghost) in query.iter() {
         let ghost_renderer = GhostRenderer::new(ghost.ghost_type);
         let z_index = ghost_renderer.get_z_index();
 
         // 计算幽灵位置
-        let px = offset_x + ghost.tile_pos.x as f32 * TILE_SIZE;
-        let py = offset_y - ghost.tile_pos.y as f32 * TILE_SIZE;
+        let world = grid_to_world(&map_data, ghost.position(&map_data, 0.0));
 
         commands
             .entity(entity)
             .insert(GhostUI)
             .insert(ghost_renderer.clone())
             .insert(Visibility::default())
-            .insert(Transform::from_xyz(px + HALF, py - HALF, z_index))
+            .insert(Transform::from_translation(world.extend(z_index)))
             .with_children(|parent| {
                 draw_ghost(parent, ghost, &ghost_renderer);
             });
@@ -98,17 +98,15 @@ pub fn update_ghost_ui(
     mut commands: Commands,
     map_data: Res<crate::MapData>,
     mut query: Query<(Entity, &Ghost, &mut GhostRenderer, &mut Transform), With<GhostUI>>,
+    fixed_time: Res<Time<Fixed>>,
 ) {
-    // 计算地图偏移量
-    let offset_x = -((map_data.width as f32) * TILE_SIZE) / 2.0;
-    let offset_y = ((map_data.height as f32) * TILE_SIZE) / 2.0;
-
+    // 模拟以固定频率运行，渲染时补上距离上一个模拟帧的时间
+    let overstep = fixed_time.overstep().as_secs_f32();
     for (entity, ghost, mut renderer, mut transform) in query.iter_mut() {
-        // 更新位置
-        let px = offset_x + ghost.tile_pos.x as f32 * TILE_SIZE;
-        let py = offset_y - ghost.tile_pos.y as f32 * TILE_SIZE;
-        transform.translation.x = px + HALF;
-        transform.translation.y = py - HALF;
+        // 更新位置，在两格之间和两个模拟帧之间平滑插值
+        let world = grid_to_world(&map_data, ghost.position(&map_data, overstep));
+        transform.translation.x = world.x;
+        transform.translation.y = world.y;
 
         // 更新z轴
         transform.translation.z = renderer.get_z_index();
@@ -116,6 +114,7 @@ pub fn update_ghost_ui(
         // 更新渲染器状态
         renderer.is_frightened = ghost.mode == GhostMode::Frightened;
         renderer.frightened_timer = ghost.frightened_time;
+        renderer.frightened_flashes = ghost.frightened_flashes;
 
         // 清理旧的形状组件
         commands.entity(entity).despawn_children();
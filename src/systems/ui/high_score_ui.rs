@@ -0,0 +1,166 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use crate::{
+    BACKGROUND_COLOR, BUTTON_TEXT_COLOR, BestScore, HUD_FONT_SIZE, HighScores, MAP_PATH,
+    MenuButton, QUIT_BUTTON_BORDER_COLOR, ReplayPlayback, Rewind, Score, TITLE_COLOR,
+    localization::LanguageSettings,
+    spawn_button,
+    systems::fonts::{FontAssets, get_font_for_language},
+};
+
+#[derive(Component)]
+pub struct HighScoresUI;
+
+/// 显示分数和最高分的 HUD，随关卡一起清理
+#[derive(Component)]
+pub struct ScoreHud;
+
+/// 设置排行榜页面，列出当前地图的前几名
+pub fn setup_high_scores_ui(
+    mut commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    high_scores: Res<HighScores>,
+) {
+    let font = get_font_for_language(&language_settings, &font_assets);
+    let texts = language_settings.get_texts();
+
+    let map_name = Path::new(MAP_PATH)
+        .file_stem()
+        .map_or_else(|| MAP_PATH.into(), |stem| stem.to_string_lossy());
+    let entries = high_scores.table.entries();
+    let lines = if entries.is_empty() {
+        vec![texts.no_high_scores.to_string()]
+    } else {
+        entries
+            .iter()
+            .enumerate()
+            .map(|(rank, entry)| {
+                format!(
+                    "{:>2}. {}  {:>7}  {} {}",
+                    rank + 1,
+                    entry.initials,
+                    entry.score,
+                    texts.level_label,
+                    entry.level
+                )
+            })
+            .collect()
+    };
+
+    // 根UI容器
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BACKGROUND_COLOR),
+            HighScoresUI,
+        ))
+        .with_children(|parent| {
+            // 排行榜标题
+            parent.spawn((
+                Text::new(format!("{} - {}", texts.high_scores_title, map_name)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+                Node {
+                    margin: UiRect::bottom(Val::Px(16.0)),
+                    ..default()
+                },
+            ));
+
+            for line in lines {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(BUTTON_TEXT_COLOR),
+                    Node {
+                        margin: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                ));
+            }
+
+            spawn_button(
+                parent,
+                texts.back_button,
+                &font,
+                QUIT_BUTTON_BORDER_COLOR,
+                MenuButton,
+            );
+        });
+}
+
+pub fn cleanup_high_scores_ui(mut commands: Commands, query: Query<Entity, With<HighScoresUI>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 生成右上角的分数 HUD
+pub fn spawn_score_hud(
+    mut commands: Commands,
+    query: Query<(), With<ScoreHud>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if !query.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            right: Val::Px(8.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font: get_font_for_language(&language_settings, &font_assets),
+            font_size: HUD_FONT_SIZE,
+            ..default()
+        },
+        TextColor(BUTTON_TEXT_COLOR),
+        ScoreHud,
+    ));
+}
+
+/// 刷新分数 HUD，能计入排行榜的一局超过最高分时最高分跟着变化
+pub fn update_score_hud(
+    score: Res<Score>,
+    best_score: Res<BestScore>,
+    rewind: Res<Rewind>,
+    playback: Option<Res<ReplayPlayback>>,
+    language_settings: Res<LanguageSettings>,
+    mut query: Query<&mut Text, With<ScoreHud>>,
+) {
+    let texts = language_settings.get_texts();
+    let best = if playback.is_none() && !rewind.used {
+        best_score.value.max(score.value)
+    } else {
+        best_score.value
+    };
+
+    for mut text in &mut query {
+        text.0 = format!(
+            "{} {}  {} {}",
+            texts.score_label, score.value, texts.best_score_label, best
+        );
+    }
+}
@@ -2,9 +2,10 @@ use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
 
 use crate::{
-    Ghost, GhostType, HALF, PELLET_COLOR, PELLET_RADIUS, PLAYER_COLOR, PLAYER_RADIUS,
-    POWER_PELLET_COLOR, POWER_PELLET_RADIUS, Pellet, PelletType, Player, TILE_SIZE, WALL_COLOR,
-    WALL_THICKNESS, Z_MAP, Z_PELLET, Z_PLAYER, Z_WALL,
+    FRUIT_RADIUS, Fruit, Ghost, GhostType, HALF, HOUSE_DOOR_COLOR, HOUSE_DOOR_THICKNESS,
+    PELLET_COLOR, PELLET_RADIUS, PLAYER_COLOR, PLAYER_RADIUS, POWER_PELLET_COLOR,
+    POWER_PELLET_RADIUS, Pellet, PelletType, Player, TILE_SIZE, WALL_COLOR, WALL_THICKNESS,
+    Z_FRUIT, Z_MAP, Z_PELLET, Z_PLAYER, Z_WALL,
     components::{MapData, TileType},
     is_power_pellet,
     ui::player_ui::{PelletUI, PlayerUI},
@@ -18,6 +19,11 @@ pub struct WallUI;
 
 /// 绘制地图 UI
 pub fn setup_map_ui(mut commands: Commands, map_data: Res<MapData>) {
+    spawn_map(&mut commands, &map_data);
+}
+
+/// 根据地图数据生成地图、豆子、玩家和幽灵实体
+pub fn spawn_map(commands: &mut Commands, map_data: &MapData) {
     // 世界坐标系以屏幕中心的(offset_x, offset_y)为原点，向右为x轴正方向，向上为y轴正方向
     let offset_x = -((map_data.width as f32) * TILE_SIZE) / 2.0;
     let offset_y = ((map_data.height as f32) * TILE_SIZE) / 2.0;
@@ -43,7 +49,7 @@ pub fn setup_map_ui(mut commands: Commands, map_data: Res<MapData>) {
 
             match tile {
                 TileType::Wall => {
-                    calc_lines(&mut lines, &map_data, x, y, px, py);
+                    calc_lines(&mut lines, map_data, x, y, px, py);
                     // 绘制线段
                     for (a, b) in &lines {
                         commands.entity(root).with_children(|parent| {
@@ -58,6 +64,22 @@ pub fn setup_map_ui(mut commands: Commands, map_data: Res<MapData>) {
                         });
                     }
                 }
+                TileType::HouseDoor => {
+                    // 基地门画在格子中间
+                    let door = shapes::Line(
+                        Vec2::new(px, py - HALF),
+                        Vec2::new(px + TILE_SIZE, py - HALF),
+                    );
+                    commands.entity(root).with_children(|parent| {
+                        parent.spawn((
+                            ShapeBuilder::with(&door)
+                                .stroke(Stroke::new(HOUSE_DOOR_COLOR, HOUSE_DOOR_THICKNESS))
+                                .build(),
+                            Transform::from_xyz(0.0, 0.0, Z_WALL),
+                            WallUI,
+                        ));
+                    });
+                }
                 TileType::Pellet => {
                     // 判断豆子类型
                     let (pellet, pellet_radius, pellet_color) = get_pellet_info(x, y);
@@ -80,13 +102,13 @@ pub fn setup_map_ui(mut commands: Commands, map_data: Res<MapData>) {
                 TileType::Player => {
                     let circle = shapes::Circle {
                         radius: PLAYER_RADIUS,
-                        center: Vec2::new(px + HALF, py - HALF),
+                        center: Vec2::ZERO,
                     };
                     commands.spawn((
                         ShapeBuilder::with(&circle)
                             .fill(Fill::color(PLAYER_COLOR))
                             .build(),
-                        Transform::from_xyz(0.0, 0.0, Z_PLAYER),
+                        Transform::from_xyz(px + HALF, py - HALF, Z_PLAYER),
                         PlayerUI,
                         Player::new(x as i32, y as i32),
                     ));
@@ -148,6 +170,41 @@ fn calc_lines(
     }
 }
 
+/// 绘制奖励水果
+pub fn spawn_fruit_ui(commands: &mut Commands, map_data: &MapData, fruit: Fruit) {
+    let offset_x = -((map_data.width as f32) * TILE_SIZE) / 2.0;
+    let offset_y = ((map_data.height as f32) * TILE_SIZE) / 2.0;
+    let px = offset_x + fruit.position.x as f32 * TILE_SIZE;
+    let py = offset_y - fruit.position.y as f32 * TILE_SIZE;
+
+    let circle = shapes::Circle {
+        radius: FRUIT_RADIUS,
+        center: Vec2::new(px + HALF, py - HALF),
+    };
+    commands.spawn((
+        ShapeBuilder::with(&circle)
+            .fill(Fill::color(Color::from(fruit.fruit_type)))
+            .build(),
+        Transform::from_xyz(0.0, 0.0, Z_FRUIT),
+        fruit,
+    ));
+}
+
+/// 地图格中心点的世界坐标
+pub fn tile_to_world(map_data: &MapData, pos: IVec2) -> Vec2 {
+    grid_to_world(map_data, pos.as_vec2())
+}
+
+/// 连续坐标（单位：格）对应的世界坐标
+pub fn grid_to_world(map_data: &MapData, pos: Vec2) -> Vec2 {
+    let offset_x = -((map_data.width as f32) * TILE_SIZE) / 2.0;
+    let offset_y = ((map_data.height as f32) * TILE_SIZE) / 2.0;
+    Vec2::new(
+        offset_x + pos.x * TILE_SIZE + HALF,
+        offset_y - pos.y * TILE_SIZE - HALF,
+    )
+}
+
 /// 获取豆子渲染信息
 fn get_pellet_info(x: usize, y: usize) -> (Pellet, f32, Color) {
     if is_power_pellet(x, y) {
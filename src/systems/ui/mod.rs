@@ -1,9 +1,25 @@
+mod achievement_ui;
+mod game_over_ui;
 mod game_ui;
+mod ghost_run_ui;
+mod ghost_ui;
+mod high_score_ui;
 mod map_ui;
+mod pause_ui;
+mod popup_ui;
 mod player_ui;
-mod ghost_ui;
+mod profile_ui;
+mod stats_ui;
 
+pub use achievement_ui::*;
+pub use game_over_ui::*;
 pub use game_ui::*;
+pub use ghost_run_ui::*;
+pub use ghost_ui::*;
+pub use high_score_ui::*;
 pub use map_ui::*;
+pub use pause_ui::*;
+pub use popup_ui::*;
 pub use player_ui::*;
-pub use ghost_ui::*;
+pub use profile_ui::*;
+pub use stats_ui::*;
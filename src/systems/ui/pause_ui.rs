@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::{
+    BUTTON_TEXT_COLOR, OVERLAY_COLOR, QUIT_BUTTON_BORDER_COLOR, RESTART_BUTTON_BORDER_COLOR,
+    START_BUTTON_BORDER_COLOR, TITLE_COLOR,
+    localization::LanguageSettings,
+    systems::fonts::{FontAssets, get_font_for_language},
+};
+
+#[derive(Component)]
+pub struct PauseUI;
+
+#[derive(Component)]
+pub struct ResumeButton;
+
+#[derive(Component)]
+pub struct RestartButton;
+
+#[derive(Component)]
+pub struct MenuButton;
+
+/// 设置暂停菜单UI，覆盖在游戏画面之上
+pub fn setup_pause_ui(
+    mut commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    let font = get_font_for_language(&language_settings, &font_assets);
+    let texts = language_settings.get_texts();
+
+    // 根UI容器
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(OVERLAY_COLOR),
+            GlobalZIndex(1),
+            PauseUI,
+        ))
+        .with_children(|parent| {
+            // 暂停标题
+            parent.spawn((
+                Text::new(texts.paused_title),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 60.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            spawn_button(
+                parent,
+                texts.resume_button,
+                &font,
+                START_BUTTON_BORDER_COLOR,
+                ResumeButton,
+            );
+            spawn_button(
+                parent,
+                texts.restart_button,
+                &font,
+                RESTART_BUTTON_BORDER_COLOR,
+                RestartButton,
+            );
+            spawn_button(
+                parent,
+                texts.menu_button,
+                &font,
+                QUIT_BUTTON_BORDER_COLOR,
+                MenuButton,
+            );
+        });
+}
+
+pub fn cleanup_pause_ui(mut commands: Commands, query: Query<Entity, With<PauseUI>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 生成带文字的按钮
+pub fn spawn_button(
+    parent: &mut ChildSpawnerCommands,
+    text: &str,
+    font: &Handle<Font>,
+    border_color: Color,
+    marker: impl Component,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(260.0),
+                height: Val::Px(65.0),
+                margin: UiRect::all(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BorderColor::all(border_color),
+            marker,
+        ))
+        .with_children(|btn| {
+            btn.spawn((
+                Text::new(text),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 30.0,
+                    ..default()
+                },
+                TextColor(BUTTON_TEXT_COLOR),
+            ));
+        });
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{HALF, MapData, Player, TILE_SIZE, Z_PLAYER};
+use crate::{MapData, Player, Z_PLAYER, grid_to_world};
 
 #[derive(Component)]
 pub struct PlayerUI;
@@ -8,21 +8,16 @@ pub struct PlayerUI;
 #[derive(Component)]
 pub struct PelletUI;
 
-/// 同步玩家实体与逻辑位置
+/// 同步玩家实体与逻辑位置，在两格之间和两个模拟帧之间平滑插值
 pub fn sync_player_ui(
     mut player_query: Query<(&Player, &mut Transform), With<PlayerUI>>,
     map_data: Res<MapData>,
+    fixed_time: Res<Time<Fixed>>,
 ) {
-    let offset_x = -((map_data.width as f32) * TILE_SIZE) / 2.0;
-    let offset_y = ((map_data.height as f32) * TILE_SIZE) / 2.0;
+    // 模拟以固定频率运行，渲染时补上距离上一个模拟帧的时间
+    let overstep = fixed_time.overstep().as_secs_f32();
     for (player, mut transform) in &mut player_query {
-        let world_x = offset_x + player.tile_pos.x as f32 * TILE_SIZE + HALF;
-        let world_y = offset_y - player.tile_pos.y as f32 * TILE_SIZE - HALF;
-        // 不要问这个偏移量是怎么来的，问就是硬数的
-        transform.translation = Vec3::new(
-            world_x + 6.0 * TILE_SIZE + HALF,
-            world_y + 8.0 * TILE_SIZE,
-            Z_PLAYER,
-        );
+        let world = grid_to_world(&map_data, player.position(&map_data, overstep));
+        transform.translation = world.extend(Z_PLAYER);
     }
 }
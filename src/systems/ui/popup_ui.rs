@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use crate::{
+    FontAssets, FruitEatenEvent, GhostEatenEvent, MapData, POPUP_COLOR, POPUP_DURATION,
+    POPUP_FONT_SIZE, Z_POPUP, tile_to_world,
+};
+
+/// 得分提示，短暂显示后消失
+#[derive(Component)]
+pub struct ScorePopup {
+    pub remaining_time: f32,
+}
+
+/// 吃幽灵或水果时在原地显示获得的分数
+pub fn spawn_score_popups(
+    mut commands: Commands,
+    mut ghost_reader: MessageReader<GhostEatenEvent>,
+    mut fruit_reader: MessageReader<FruitEatenEvent>,
+    map_data: Res<MapData>,
+    font_assets: Res<FontAssets>,
+) {
+    let popups = ghost_reader
+        .read()
+        .map(|evt| (evt.position, evt.points))
+        .chain(fruit_reader.read().map(|evt| (evt.position, evt.points)));
+
+    for (position, points) in popups {
+        let world = tile_to_world(&map_data, position);
+        commands.spawn((
+            Text2d::new(points.to_string()),
+            TextFont {
+                font: font_assets.default_font.clone(),
+                font_size: POPUP_FONT_SIZE,
+                ..default()
+            },
+            TextColor(POPUP_COLOR),
+            Transform::from_xyz(world.x, world.y, Z_POPUP),
+            ScorePopup {
+                remaining_time: POPUP_DURATION,
+            },
+        ));
+    }
+}
+
+/// 更新得分提示，超时后移除
+pub fn update_score_popups(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut ScorePopup)>,
+    time: Res<Time>,
+) {
+    for (entity, mut popup) in &mut query {
+        popup.remaining_time -= time.delta_secs();
+        if popup.remaining_time <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use bevy::prelude::*;
+
+use crate::{
+    ActiveProfile, BACKGROUND_COLOR, BUTTON_TEXT_COLOR, Difficulty, PlayMode, ProfileMenu,
+    QUIT_BUTTON_BORDER_COLOR, REPLAY_BUTTON_BORDER_COLOR, START_BUTTON_BORDER_COLOR, TITLE_COLOR,
+    localization::{LanguageSettings, LocalizedTexts},
+    systems::fonts::{FontAssets, get_font_for_language},
+};
+
+#[derive(Component)]
+pub struct ProfilesUI;
+
+/// 选择档案，值为档案名
+#[derive(Component)]
+pub struct SelectProfileButton(pub String);
+
+#[derive(Component)]
+pub struct NewProfileButton;
+
+/// 重命名档案，值为档案名
+#[derive(Component)]
+pub struct RenameProfileButton(pub String);
+
+/// 删除档案，值为档案名
+#[derive(Component)]
+pub struct DeleteProfileButton(pub String);
+
+/// 主菜单中可以修改的档案设置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileSetting {
+    /// 切换到档案页面
+    Profile,
+    Difficulty,
+    KeyBindings,
+    /// 休闲或排位，排位时不能倒带
+    Mode,
+}
+
+#[derive(Component)]
+pub struct ProfileSettingButton(pub ProfileSetting);
+
+/// 档案设置按钮上的文字，设置变化时更新
+#[derive(Component)]
+pub struct ProfileSettingText(pub ProfileSetting);
+
+/// 生成档案页面，档案列表或输入状态变化时重新生成
+pub fn setup_profiles_ui(
+    mut commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    menu: Res<ProfileMenu>,
+    active: Res<ActiveProfile>,
+    query: Query<Entity, With<ProfilesUI>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+
+    let font = get_font_for_language(&language_settings, &font_assets);
+    let texts = language_settings.get_texts();
+    let active_name = active.0.as_ref().map(|profile| profile.name.as_str());
+
+    // 根UI容器
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BACKGROUND_COLOR),
+            ProfilesUI,
+        ))
+        .with_children(|parent| {
+            // 档案标题
+            parent.spawn((
+                Text::new(texts.profiles_title),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+                Node {
+                    margin: UiRect::bottom(Val::Px(16.0)),
+                    ..default()
+                },
+            ));
+
+            if menu.profiles.is_empty() {
+                parent.spawn((
+                    Text::new(texts.no_profiles),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(BUTTON_TEXT_COLOR),
+                ));
+            }
+
+            // 每个档案一行：选择、重命名、删除
+            for name in &menu.profiles {
+                let delete_text = if menu.confirm_delete.as_ref() == Some(name) {
+                    texts.confirm_delete_button
+                } else {
+                    texts.delete_button
+                };
+                let select_border = if active_name == Some(name.as_str()) {
+                    START_BUTTON_BORDER_COLOR
+                } else {
+                    TITLE_COLOR
+                };
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::all(Val::Px(4.0)),
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        spawn_row_button(
+                            row,
+                            name,
+                            &font,
+                            260.0,
+                            select_border,
+                            SelectProfileButton(name.clone()),
+                        );
+                        spawn_row_button(
+                            row,
+                            texts.rename_button,
+                            &font,
+                            120.0,
+                            REPLAY_BUTTON_BORDER_COLOR,
+                            RenameProfileButton(name.clone()),
+                        );
+                        spawn_row_button(
+                            row,
+                            delete_text,
+                            &font,
+                            120.0,
+                            QUIT_BUTTON_BORDER_COLOR,
+                            DeleteProfileButton(name.clone()),
+                        );
+                    });
+            }
+
+            // 输入档案名时显示输入框，否则显示新建按钮
+            match &menu.entry {
+                Some(entry) => {
+                    parent.spawn((
+                        Text::new(format!("{}: {}_", texts.profile_name_label, entry.name)),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 30.0,
+                            ..default()
+                        },
+                        TextColor(TITLE_COLOR),
+                        Node {
+                            margin: UiRect::top(Val::Px(16.0)),
+                            ..default()
+                        },
+                    ));
+                    parent.spawn((
+                        Text::new(texts.profile_name_hint),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(BUTTON_TEXT_COLOR),
+                    ));
+                }
+                None => {
+                    parent
+                        .spawn(Node {
+                            margin: UiRect::top(Val::Px(16.0)),
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            spawn_row_button(
+                                row,
+                                texts.new_profile_button,
+                                &font,
+                                260.0,
+                                START_BUTTON_BORDER_COLOR,
+                                NewProfileButton,
+                            );
+                        });
+                }
+            }
+
+            if let Some(error) = &menu.error {
+                parent.spawn((
+                    Text::new(error.clone()),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(QUIT_BUTTON_BORDER_COLOR),
+                    Node {
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+pub fn cleanup_profiles_ui(mut commands: Commands, query: Query<Entity, With<ProfilesUI>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 在主菜单顶部生成当前档案的设置按钮
+pub fn spawn_profile_settings(
+    parent: &mut ChildSpawnerCommands,
+    active: &ActiveProfile,
+    texts: &LocalizedTexts,
+    font: &Handle<Font>,
+) {
+    parent
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(10.0),
+            ..default()
+        })
+        .with_children(|row| {
+            for setting in [
+                ProfileSetting::Profile,
+                ProfileSetting::Difficulty,
+                ProfileSetting::KeyBindings,
+                ProfileSetting::Mode,
+            ] {
+                row.spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(220.0),
+                        height: Val::Px(36.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor::all(REPLAY_BUTTON_BORDER_COLOR),
+                    ProfileSettingButton(setting),
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new(profile_setting_text(setting, active, texts)),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(BUTTON_TEXT_COLOR),
+                        ProfileSettingText(setting),
+                    ));
+                });
+            }
+        });
+}
+
+/// 当前档案的设置变化后更新主菜单的按钮文字
+pub fn update_profile_setting_texts(
+    active: Res<ActiveProfile>,
+    language_settings: Res<LanguageSettings>,
+    mut query: Query<(&mut Text, &ProfileSettingText)>,
+) {
+    let texts = language_settings.get_texts();
+    for (mut text, ProfileSettingText(setting)) in &mut query {
+        **text = profile_setting_text(*setting, &active, texts);
+    }
+}
+
+/// 设置按钮上显示的文字，如 "Difficulty: Normal"
+fn profile_setting_text(
+    setting: ProfileSetting,
+    active: &ActiveProfile,
+    texts: &LocalizedTexts,
+) -> String {
+    let Some(profile) = &active.0 else {
+        return format!("{}: -", texts.profile_label);
+    };
+    match setting {
+        ProfileSetting::Profile => format!("{}: {}", texts.profile_label, profile.name),
+        ProfileSetting::Difficulty => {
+            let difficulty = match profile.settings.difficulty {
+                Difficulty::Easy => texts.easy_difficulty,
+                Difficulty::Normal => texts.normal_difficulty,
+                Difficulty::Hard => texts.hard_difficulty,
+            };
+            format!("{}: {}", texts.difficulty_label, difficulty)
+        }
+        ProfileSetting::KeyBindings => format!(
+            "{}: {}",
+            texts.keys_label,
+            profile
+                .settings
+                .key_bindings
+                .preset_name()
+                .unwrap_or(texts.custom_keys)
+        ),
+        ProfileSetting::Mode => {
+            let mode = match profile.settings.mode {
+                PlayMode::Casual => texts.casual_mode,
+                PlayMode::Ranked => texts.ranked_mode,
+            };
+            format!("{}: {}", texts.mode_label, mode)
+        }
+    }
+}
+
+/// 档案页面中较小的按钮
+fn spawn_row_button(
+    parent: &mut ChildSpawnerCommands,
+    text: &str,
+    font: &Handle<Font>,
+    width: f32,
+    border_color: Color,
+    marker: impl Component,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(50.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BorderColor::all(border_color),
+            marker,
+        ))
+        .with_children(|btn| {
+            btn.spawn((
+                Text::new(text),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(BUTTON_TEXT_COLOR),
+            ));
+        });
+}
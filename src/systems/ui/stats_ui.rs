@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+
+use crate::{
+    BACKGROUND_COLOR, BUTTON_TEXT_COLOR, GhostType, LifetimeStats, MenuButton,
+    QUIT_BUTTON_BORDER_COLOR, TITLE_COLOR,
+    localization::LanguageSettings,
+    spawn_button,
+    systems::fonts::{FontAssets, get_font_for_language},
+};
+
+#[derive(Component)]
+pub struct StatisticsUI;
+
+/// 设置统计页面，显示跨多次运行累计的统计
+pub fn setup_statistics_ui(
+    mut commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    stats: Res<LifetimeStats>,
+) {
+    let font = get_font_for_language(&language_settings, &font_assets);
+    let texts = language_settings.get_texts();
+
+    let mut lines = vec![
+        format!("{}: {}", texts.pellets_label, stats.pellets_eaten),
+        format!(
+            "{}: {}",
+            texts.power_pellets_label, stats.power_pellets_eaten
+        ),
+        format!("{}: {}", texts.levels_cleared_label, stats.levels_cleared),
+        format!(
+            "{}: {}",
+            texts.fastest_level_label,
+            stats
+                .fastest_level_clear
+                .map_or_else(|| "-".into(), |secs| format_duration(f64::from(secs)))
+        ),
+        format!(
+            "{}: {}",
+            texts.play_time_label,
+            format_duration(stats.play_time)
+        ),
+    ];
+    // 每种幽灵被吃掉和抓住玩家的次数
+    for ghost_type in GhostType::ALL {
+        lines.push(format!(
+            "{:?}  {} {}  {} {}",
+            ghost_type,
+            texts.ghosts_eaten_label,
+            stats.ghosts_eaten_by(ghost_type),
+            texts.deaths_label,
+            stats.deaths_by(ghost_type)
+        ));
+    }
+
+    // 根UI容器
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BACKGROUND_COLOR),
+            StatisticsUI,
+        ))
+        .with_children(|parent| {
+            // 统计标题
+            parent.spawn((
+                Text::new(texts.statistics_title),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+                Node {
+                    margin: UiRect::bottom(Val::Px(16.0)),
+                    ..default()
+                },
+            ));
+
+            for line in lines {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(BUTTON_TEXT_COLOR),
+                    Node {
+                        margin: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                ));
+            }
+
+            spawn_button(
+                parent,
+                texts.back_button,
+                &font,
+                QUIT_BUTTON_BORDER_COLOR,
+                MenuButton,
+            );
+        });
+}
+
+pub fn cleanup_statistics_ui(mut commands: Commands, query: Query<Entity, With<StatisticsUI>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 把秒数显示为 h:mm:ss 或 m:ss.s
+fn format_duration(secs: f64) -> String {
+    let tenths = (secs * 10.0).round() as u64;
+    let total = tenths / 10;
+    let hours = total / 3600;
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, total / 60 % 60, total % 60)
+    } else {
+        format!("{}:{:02}.{}", total / 60, total % 60, tenths % 10)
+    }
+}